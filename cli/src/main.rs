@@ -1,10 +1,10 @@
 extern crate clap;
-use clap::{Arg, App};
+use clap::{Arg, Command};
 use std::path::Path;
 use std::ffi::OsStr;
 use std::time::{Instant};
-use lazy::semantic_analyzer::{file_host::{FSFileHost, FileHost}};
-use lazy::errors::builder::ErrorFormatter;
+use lazy::semantic_analyzer::{file_host::{FSFileHost, FileHost}, lints::{LintConfig, LintLevel}};
+use lazy::errors::{ErrorCollector, FileId, builder::{ErrorFormatter, ColorChoice, set_color_choice}, locale::{set_language, CATALOGS}, sarif::SarifFormatter, fix::apply_until_fixed, diagnostics::{Diagnostics, parse_code, format_code}, explanations::get_explanation};
 
 fn get_extention_validity(filename: &str) -> Option<&str> {
     Path::new(filename)
@@ -12,45 +12,242 @@ fn get_extention_validity(filename: &str) -> Option<&str> {
         .and_then(OsStr::to_str)
 }
 
+// Errors, warnings and notes of the file and of every module it imports
+fn get_diagnostics(files: &mut FSFileHost, filename: &str) -> ErrorCollector {
+    let mut diagnostics = match files.create(filename) {
+        Err(errors) => errors,
+        Ok(Some(module)) => ErrorCollector::new(module.file),
+        Ok(None) => ErrorCollector::new(FileId::default())
+    };
+    for module in files.files.values() {
+        diagnostics.collected.extend(module.warnings.iter().cloned());
+    }
+    diagnostics
+}
+
+// Invalid options and configuration are reported on stderr, so they don't get mixed with machine-readable diagnostics
+fn fail(message: String) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(2)
+}
+
+// Reads the `[lints]` table of the closest `lazy.toml`, starting from the directory of the file
+fn load_lint_config(filename: &str) -> Result<LintConfig, String> {
+    let mut config = LintConfig::new();
+    let file = std::fs::canonicalize(filename).map_err(|error| format!("Could not read {}. Error: {}", filename, error))?;
+    let path = if let Some(path) = file.ancestors().skip(1).map(|dir| dir.join("lazy.toml")).find(|path| path.exists()) { path } else {
+        return Ok(config);
+    };
+    let text = std::fs::read_to_string(&path).map_err(|error| format!("Could not read {}. Error: {}", path.display(), error))?;
+    let value = text.parse::<toml::Value>().map_err(|error| format!("Could not parse {}. Error: {}", path.display(), error))?;
+    if let Some(lints) = value.get("lints").and_then(|lints| lints.as_table()) {
+        for (name, level) in lints {
+            let level = level.as_str().and_then(LintLevel::parse).ok_or_else(|| format!("Invalid level for lint \"{}\" in {}. Expected \"allow\", \"warn\" or \"deny\".", name, path.display()))?;
+            if !config.set(name, level) {
+                return Err(format!("Unknown lint \"{}\" in {}.", name, path.display()));
+            }
+        }
+    }
+    Ok(config)
+}
+
+// Applies all machine-applicable suggestions, until there are no more left
+fn fix_file(filename: &str) {
+    let lints = load_lint_config(filename).unwrap_or_else(|error| fail(error));
+    let result = apply_until_fixed(|| {
+        let mut files = FSFileHost::new();
+        files.lints = lints.clone();
+        let mut errors = get_diagnostics(&mut files, filename);
+        errors.normalize();
+        (files, errors)
+    }, |name, content| std::fs::write(name, content).map_err(|error| format!("Could not write to {}. Error: {}", name, error)));
+    match result {
+        Ok(total) => println!("Applied {} fix{}.", total, if total == 1 { "" } else { "es" }),
+        Err(error) => println!("{}", error)
+    }
+}
+
 fn main() {
-    let matches = App::new("lazy")
+    let matches = Command::new("lazy")
     .version("0.0.0") 
     .about("A command-line interface to interact with the lazy programming language")
     .arg(
     Arg::new("run")
     .short('r')
     .long("run")
-    .about("Runs a lazy file")
+    .help("Runs a lazy file")
     .takes_value(true)
     )
     .arg(
     Arg::new("time")
     .short('t')
     .long("time")
-    .about("Shows you the parsing time of the code")
+    .help("Shows you the parsing time of the code")
     .takes_value(false)
+    .global(true)
+    )
+    .arg(
+    Arg::new("deny-warnings")
+    .long("deny-warnings")
+    .help("Treats all warnings as errors")
+    .takes_value(false)
+    .global(true)
+    )
+    .arg(
+    Arg::new("allow")
+    .long("allow")
+    .help("Hides all warnings and notes with the given code, or all diagnostics of the given lint")
+    .takes_value(true)
+    .multiple_occurrences(true)
+    .value_name("code or lint")
+    .global(true)
+    )
+    .arg(
+    Arg::new("error-format")
+    .long("error-format")
+    .help("The format of the diagnostics")
+    .takes_value(true)
+    .possible_values(["human", "json", "sarif"])
+    .default_value("human")
+    .global(true)
+    )
+    .arg(
+    Arg::new("color")
+    .long("color")
+    .help("When to color the output. \"auto\" doesn't use colors if NO_COLOR is set or the output isn't a terminal")
+    .takes_value(true)
+    .possible_values(["auto", "always", "never"])
+    .default_value("auto")
+    .global(true)
+    )
+    .arg(
+    Arg::new("error-limit")
+    .long("error-limit")
+    .help("Stops showing diagnostics after this many errors. 0 shows all of them")
+    .takes_value(true)
+    .value_name("N")
+    .default_value("0")
+    .global(true)
+    )
+    .arg(
+    Arg::new("lang")
+    .long("lang")
+    .help("The language of the diagnostics. Defaults to the language in the LANG environment variable, or English")
+    .takes_value(true)
+    .global(true)
+    )
+    .subcommand(
+    Command::new("check")
+    .about("Checks a lazy file for errors without running it")
+    .arg(
+    Arg::new("file")
+    .help("The lazy file to check")
+    .required(true)
+    .takes_value(true)
+    )
+    )
+    .subcommand(
+    Command::new("fix")
+    .about("Applies all machine-applicable suggestions to a lazy file")
+    .arg(
+    Arg::new("file")
+    .help("The lazy file to fix")
+    .required(true)
+    .takes_value(true)
+    )
+    )
+    .subcommand(
+    Command::new("explain")
+    .about("Shows a detailed explanation of a diagnostic")
+    .arg(
+    Arg::new("code")
+    .help("The code of the diagnostic, for example E1009")
+    .required(true)
+    .takes_value(true)
+    )
     )
     .get_matches();
 
-if let Some(exe_file) = matches.value_of("run") {
+set_color_choice(match matches.value_of("color") {
+    Some("always") => ColorChoice::Always,
+    Some("never") => ColorChoice::Never,
+    _ => ColorChoice::Auto
+});
+
+if let Some(lang) = matches.value_of("lang") {
+    if !set_language(lang) {
+        fail(format!("Unknown language \"{}\". Available languages: {}.", lang, CATALOGS.iter().map(|(name, _)| *name).collect::<Vec<&str>>().join(", ")));
+    }
+} else if let Ok(lang) = std::env::var("LANG") {
+    // Unsupported system languages fall back to English
+    set_language(&lang);
+}
+
+if let Some(explain) = matches.subcommand_matches("explain") {
+    let code = explain.value_of("code").unwrap();
+    match parse_code(code).and_then(|code| Some((Diagnostics::from_code(code)?, get_explanation(code)?))) {
+        Some((diagnostic, explanation)) => println!("{}: {}\n\n{}", format_code(diagnostic.code), diagnostic.message(), explanation),
+        None => fail(format!("Unknown diagnostic code \"{}\".", code))
+    }
+    return;
+}
+
+if let Some(fix) = matches.subcommand_matches("fix") {
+    let file = fix.value_of("file").unwrap();
+    if Path::new(file).exists() { fix_file(file) }
+    else { println!("Path does not exist.") };
+    return;
+}
+
+let (exe_file, matches) = if let Some(check) = matches.subcommand_matches("check") {
+    (check.value_of("file"), check)
+} else {
+    (matches.value_of("run"), &matches)
+};
+
+if let Some(exe_file) = exe_file {
     if Path::new(&exe_file).exists() {
-        if get_extention_validity(&exe_file) == Some("lazy") {
+        if get_extention_validity(exe_file) == Some("lazy") {
             let mut files = FSFileHost::new();
+            files.lints = load_lint_config(exe_file).unwrap_or_else(|error| fail(error));
+            let mut allowed: Vec<u16> = vec![];
+            for code in matches.values_of("allow").into_iter().flatten() {
+                if let Some(code) = parse_code(code) { allowed.push(code) }
+                else if !files.lints.set(code, LintLevel::Allow) {
+                    fail(format!("Unknown diagnostic code or lint \"{}\".", code));
+                }
+            }
             let before = Instant::now();
-            let module = files.create(&exe_file);
-            if matches.is_present("time") {
+            let mut errors = get_diagnostics(&mut files, exe_file);
+            let error_format = matches.value_of("error-format").unwrap_or("human");
+            if matches.is_present("time") && error_format == "human" {
                 println!("Parsing took {} nanoseconds", before.elapsed().as_nanos());
             }
-            match module {
-                Err(errors) => {
-                    for error in errors.collected {
-                        println!("{}", files.format_err(&error, &errors.filename).unwrap());
+            errors.apply_levels(matches.is_present("deny-warnings"), &allowed);
+            errors.normalize();
+            match matches.value_of("error-limit").unwrap_or("0").parse::<usize>() {
+                Ok(0) => {},
+                Ok(limit) => errors.truncate_errors(limit),
+                Err(_) => fail(String::from("The error limit must be a number."))
+            }
+            match error_format {
+                "json" => {
+                    for error in &errors.collected {
+                        println!("{}", files.format_err_json(error));
                     }
                 },
-                Ok(_module) => {
-                    println!("Successfully parsed and analyzed module!");
+                "sarif" => println!("{}", files.format_sarif(&[&errors])),
+                _ => {
+                    for error in &errors.collected {
+                        println!("{}", files.format_err(error).unwrap_or_else(|| error.to_string()));
+                    }
+                    if let Some(summary) = errors.summary() { println!("{}", summary) };
                 }
             }
+            if errors.has_errors() {
+                std::process::exit(1);
+            }
+            if error_format == "human" { println!("Successfully parsed and analyzed module!") };
         }
         else{
             println!("Could not parse the source code. Error: Could not find a lazy file.")
@@ -0,0 +1,79 @@
+# English messages of all diagnostics, keyed by diagnostic code. This is the fallback for the other catalogs.
+
+## Syntax errors
+
+E1001 = Expected end of string.
+E1002 = Floating points cannot contain more than one decimal point.
+E1003 = Expected a property name.
+E1004 = Invalid character { $character }.
+E1005 = Unexpected operator { $operator }.
+E1006 = Unexpected punctuation { $punctuation }.
+E1007 = Expected semicolon at the end of the expression.
+E1008 = Expected end of block.
+E1009 = Expected { $expected }, but found { $found }.
+E1010 = Expected start of block.
+E1011 = Arrow access cannot be chained.
+E1012 = Expected delimiter { $delimiter }.
+E1013 = Too much typings provided, expected only { $expected }.
+E1014 = Empty character literal.
+E1015 = Constant variables must have an initializer.
+E1016 = Generics are not allowed here.
+E1017 = Expected end of iterator.
+E1018 = { $thing } is not allowed here.
+E1019 = Too many entry points. There can be only one.
+E1020 = Incorrect match arm expression. Match arms only accept enum variants or literals.
+E1021 = The field is already { $modifier }. Unnecessary modifier.
+E1022 = Found { $found }, which is very similar to { $expected }.
+E1023 = Invalid digit.
+E1024 = Pointless template literals. Use normal string literals instead.
+E1025 = Expected character to contain only one codepoint.
+E1026 = Expected { $expected }.
+E1027 = Unexpected { $found }.
+E1028 = Unexpected end of file.
+E1029 = Expected at least one type parameter.
+E1030 = A rest element must be the last element of a deconstruct pattern.
+E1031 = Invalid format specifier "{ $spec }".
+E1032 = Code is nested too deeply. The maximum nesting depth is { $max_depth }.
+## Semantic errors
+
+E2001 = Type { $name } not found from module { $module }.
+E2002 = Cannot find module "{ $module }".
+E2003 = Identifier "{ $name }" is already defined.
+E2004 = Detected recursive importing. Module "{ $module }" requires "{ $imported }" and vice-versa.
+E2005 = Couldn't find name { $name }.
+E2006 = A value is being used as a type here.
+E2007 = Invalid amount of type parameters. Expected { $expected }, found { $found }.
+E2008 = Field "{ $name }" is already defined.
+E2009 = Couldn't infer the type of "{ $name }". Add a type annotation.
+E2010 = Variant "{ $name }" is already defined.
+E2011 = Expected type { $expected }, found { $found }.
+E2012 = Invalid amount of values for variant "{ $name }". Expected { $expected }, found { $found }.
+E2013 = Type alias "{ $name }" refers to itself: { $path }.
+E2014 = Type { $name } cannot be used as { $param }, because it doesn't have the "{ $member }" member which { $param } requires.
+E2015 = Type { $name } cannot be used as { $param }, because its "{ $member }" member has type { $found } instead of { $expected }.
+E2016 = Type { $found } cannot be used as { $expected }, because it's missing the "{ $field }" field.
+E2017 = Type { $found } cannot be used as { $expected }, because its "{ $field }" field has the wrong type.
+E2018 = Type { $name } cannot be combined. Only structs and partials can be combined.
+E2019 = Partial { $partial } is already implemented for { $target }.
+E2020 = Type { $name } is not a partial. Only partials can be implemented.
+E2021 = Partials can only be implemented for structs and enums, not for { $name }.
+E2022 = "{ $name }" is a type, not a value.
+E2023 = Operator "{ $op }" cannot be used with { $left } and { $right }.
+E2024 = Operator "{ $op }" cannot be used with { $found }.
+E2025 = Member "{ $name }" doesn't exist on type { $target }.
+E2026 = A value of type { $found } cannot be deconstructed with this pattern.
+E2027 = Format spec "{ $spec }" cannot be used with type { $found }.
+E2028 = Missing an argument for the "{ $name }" parameter.
+E2029 = Too many arguments. Expected at most { $expected }, found { $found }.
+
+## Lints
+
+E3001 = Unused import "{ $name }".
+E3002 = Unused variable "{ $name }".
+E3003 = "{ $name }" shadows a variable from an outer scope.
+E3004 = Field "{ $name }" should have a snake case name, like "{ $expected }".
+E3005 = "{ $name }" should have an upper camel case name, like "{ $expected }".
+E3006 = Type parameter "{ $name }" is never used.
+E3007 = "{ $name }" is never used.
+E3008 = "{ $name }" is assigned to itself.
+E3009 = Unknown lint "{ $name }".
@@ -0,0 +1,80 @@
+# Spanish messages of all diagnostics, keyed by diagnostic code
+
+## Syntax errors
+
+E1001 = Se esperaba el final de la cadena.
+E1002 = Los números de punto flotante no pueden contener más de un punto decimal.
+E1003 = Se esperaba un nombre de propiedad.
+E1004 = Carácter no válido { $character }.
+E1005 = Operador inesperado { $operator }.
+E1006 = Signo de puntuación inesperado { $punctuation }.
+E1007 = Se esperaba un punto y coma al final de la expresión.
+E1008 = Se esperaba el final del bloque.
+E1009 = Se esperaba { $expected }, pero se encontró { $found }.
+E1010 = Se esperaba el inicio de un bloque.
+E1011 = Los accesos con flecha no se pueden encadenar.
+E1012 = Se esperaba el delimitador { $delimiter }.
+E1013 = Demasiados tipos. Solo se esperaba { $expected }.
+E1014 = Literal de carácter vacío.
+E1015 = Las variables constantes deben tener un valor inicial.
+E1016 = Los genéricos no están permitidos aquí.
+E1017 = Se esperaba el final del iterador.
+E1018 = { $thing } no está permitido aquí.
+E1019 = Demasiados puntos de entrada. Solo puede haber uno.
+E1020 = Expresión incorrecta en el brazo del match. Los brazos solo aceptan variantes de enum o literales.
+E1021 = El campo ya es { $modifier }. El modificador es innecesario.
+E1022 = Se encontró { $found }, que es muy similar a { $expected }.
+E1023 = Dígito no válido.
+E1024 = Literal de plantilla innecesario. Usa un literal de cadena normal.
+E1025 = Un literal de carácter solo puede contener un punto de código.
+E1026 = Se esperaba { $expected }.
+E1027 = { $found } inesperado.
+E1028 = Final de archivo inesperado.
+E1029 = Se esperaba al menos un parámetro de tipo.
+E1030 = El elemento rest debe ser el último elemento del patrón de desestructuración.
+E1031 = Especificador de formato no válido "{ $spec }".
+E1032 = El código está anidado demasiado. La profundidad máxima es { $max_depth }.
+
+## Semantic errors
+
+E2001 = No se encontró el tipo { $name } en el módulo { $module }.
+E2002 = No se encuentra el módulo "{ $module }".
+E2003 = El identificador "{ $name }" ya está definido.
+E2004 = Importación recursiva. El módulo "{ $module }" requiere "{ $imported }" y viceversa.
+E2005 = No se encontró el nombre { $name }.
+E2006 = Aquí se usa un valor como tipo.
+E2007 = Cantidad incorrecta de parámetros de tipo. Se esperaban { $expected }, se encontraron { $found }.
+E2008 = El campo "{ $name }" ya está definido.
+E2009 = No se pudo inferir el tipo de "{ $name }". Añade una anotación de tipo.
+E2010 = La variante "{ $name }" ya está definida.
+E2011 = Se esperaba el tipo { $expected }, se encontró { $found }.
+E2012 = Cantidad incorrecta de valores para la variante "{ $name }". Se esperaban { $expected }, se encontraron { $found }.
+E2013 = El alias de tipo "{ $name }" se refiere a sí mismo: { $path }.
+E2014 = El tipo { $name } no se puede usar como { $param }, porque no tiene el miembro "{ $member }" que { $param } requiere.
+E2015 = El tipo { $name } no se puede usar como { $param }, porque su miembro "{ $member }" tiene el tipo { $found } en lugar de { $expected }.
+E2016 = El tipo { $found } no se puede usar como { $expected }, porque le falta el campo "{ $field }".
+E2017 = El tipo { $found } no se puede usar como { $expected }, porque su campo "{ $field }" tiene el tipo incorrecto.
+E2018 = El tipo { $name } no se puede combinar. Solo se pueden combinar structs y parciales.
+E2019 = El parcial { $partial } ya está implementado para { $target }.
+E2020 = El tipo { $name } no es un parcial. Solo se pueden implementar parciales.
+E2021 = Los parciales solo se pueden implementar para structs y enums, no para { $name }.
+E2022 = "{ $name }" es un tipo, no un valor.
+E2023 = El operador "{ $op }" no se puede usar con { $left } y { $right }.
+E2024 = El operador "{ $op }" no se puede usar con { $found }.
+E2025 = El miembro "{ $name }" no existe en el tipo { $target }.
+E2026 = Un valor de tipo { $found } no se puede desestructurar con este patrón.
+E2027 = El formato "{ $spec }" no se puede usar con el tipo { $found }.
+E2028 = Falta un argumento para el parámetro "{ $name }".
+E2029 = Demasiados argumentos. Se esperaban como máximo { $expected }, se encontraron { $found }.
+
+## Lints
+
+E3001 = Importación sin usar "{ $name }".
+E3002 = Variable sin usar "{ $name }".
+E3003 = "{ $name }" oculta una variable de un ámbito exterior.
+E3004 = El campo "{ $name }" debería estar en snake case, por ejemplo "{ $expected }".
+E3005 = "{ $name }" debería estar en upper camel case, por ejemplo "{ $expected }".
+E3006 = El parámetro de tipo "{ $name }" nunca se usa.
+E3007 = "{ $name }" nunca se usa.
+E3008 = "{ $name }" se asigna a sí mismo.
+E3009 = Lint desconocido "{ $name }".
@@ -1,64 +1,199 @@
 
 use colored::*;
+use unicode_width::UnicodeWidthChar;
 use super::*;
 
+const TAB_WIDTH: usize = 4;
+// Lines which are wider than this are cut around the marked part
+const MAX_LINE_WIDTH: usize = 120;
+// How many lines of a multi-line range are shown before the rest are skipped
+const MAX_RANGE_LINES: usize = 6;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never
+}
+
+// Auto disables colors when the `NO_COLOR` environment variable is set, or when the output isn't a terminal
+pub fn set_color_choice(choice: ColorChoice) {
+    let enabled = match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            use std::io::IsTerminal;
+            std::env::var_os("NO_COLOR").is_none_or(|val| val.is_empty()) && std::io::stdout().is_terminal()
+        }
+    };
+    control::set_override(enabled);
+}
+
 fn get_digits_in_num(num: usize) -> usize {
-    ((num as f32).log10() as usize) + 1
+    num.to_string().len()
+}
+
+fn get_severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::Error => Color::Red,
+        Severity::Warning => Color::Yellow,
+        Severity::Note => Color::BrightBlue
+    }
+}
+
+// Splits the text to lines the same way the tokenizer counts them, without the line endings
+fn split_lines(content: &str) -> Vec<&str> {
+    content.split('\n').map(|line| line.strip_suffix('\r').unwrap_or(line)).collect()
+}
+
+// Lines start from 1. Lines past the end of the file (the range of an unexpected EOF) are empty.
+fn get_line<'a>(lines: &[&'a str], line: usize) -> &'a str {
+    lines.get(line.max(1) - 1).copied().unwrap_or("")
+}
+
+// A line as it's displayed - with expanded tabs and cut to fit, plus the display column of every character
+struct LineView {
+    text: String,
+    columns: Vec<usize>
+}
+
+impl LineView {
+
+    // `focus` is the character which must stay visible if the line gets cut
+    fn new(line: &str, focus: usize) -> Self {
+        let widths = line.chars().map(|ch| if ch == '\t' { TAB_WIDTH } else { ch.width().unwrap_or(0) }).collect::<Vec<usize>>();
+        let mut columns = Vec::with_capacity(widths.len() + 1);
+        let mut total = 0;
+        for width in &widths {
+            columns.push(total);
+            total += width;
+        }
+        columns.push(total);
+        let (window_start, window_end) = if total <= MAX_LINE_WIDTH { (0, total) } else {
+            let focus_col = columns[focus.min(widths.len())];
+            let start = if focus_col > MAX_LINE_WIDTH / 2 { (focus_col - MAX_LINE_WIDTH / 4).min(total - MAX_LINE_WIDTH) } else { 0 };
+            (start, start + MAX_LINE_WIDTH)
+        };
+        let prefix = if window_start > 0 { "..." } else { "" };
+        let mut text = String::from(prefix);
+        for (ch, (col, width)) in line.chars().zip(columns.iter().zip(&widths)) {
+            if *col < window_start || col + width > window_end { continue };
+            if ch == '\t' { text.push_str(&" ".repeat(TAB_WIDTH)) }
+            else if !ch.is_control() { text.push(ch) };
+        }
+        if total > window_end { text.push_str("...") };
+        let columns = columns.iter().map(|col| (*col).clamp(window_start, window_end) - window_start + prefix.len()).collect();
+        Self { text, columns }
+    }
+
+    // The display column of the character. Characters past the end of the line are right after it.
+    fn column(&self, col: usize) -> usize {
+        let last = self.columns.len() - 1;
+        if col > last { self.columns[last] + (col - last) } else { self.columns[col] }
+    }
+
+    // Spaces up to the start column, then the marker up to the end column (at least one)
+    fn markers(&self, start: usize, end: usize, marker: &str) -> (String, String) {
+        let start_col = self.column(start);
+        let end_col = self.column(end).max(start_col + 1);
+        (" ".repeat(start_col), marker.repeat(end_col - start_col))
+    }
+}
+
+// The lines which the suggestion changes, before and after applying it
+fn get_suggestion_diff(lines: &[&str], suggestion: &ErrorSuggestion) -> (Vec<String>, Vec<String>) {
+    let range = &suggestion.range;
+    let old = (range.start.line..=range.end.line.max(range.start.line)).map(|line| get_line(lines, line).to_string()).collect::<Vec<String>>();
+    let prefix = old[0].chars().take(range.start.col).collect::<String>();
+    let suffix = old[old.len() - 1].chars().skip(range.end.col).collect::<String>();
+    let new = format!("{}{}{}", prefix, suggestion.replacement, suffix).split('\n').map(|line| line.to_string()).collect();
+    (old, new)
 }
 
 pub trait ErrorFormatter {
-    fn get_file_contents(&self, file: &str) -> Option<&str>;
+    fn source_map(&self) -> &SourceMap;
 
-    fn format_err(&self, err: &BaseError, filename: &str) -> Option<String> {
-        let space_to_border = get_digits_in_num(err.range.end.line) + 1; // + 1 because of the padding between the number and the border
-        let mut res = format!("{}{} {} {}: {}\n", " ".repeat(space_to_border), "┎──".cyan(), filename, err.range, err.msg.to_string().red());
-        let file_contents = self.get_file_contents(filename)?;
-        let lines = file_contents.lines().collect::<Vec<&str>>();
+    fn format_err(&self, err: &BaseError) -> Option<String> {
+        let source_map = self.source_map();
+        let filename = source_map.get_name(err.range.file())?;
+        let lines = split_lines(source_map.get_content(err.range.file())?);
+        let max_line = err.labels.iter().filter_map(|label| match label.variant {
+            ErrorLabelVariants::Sub(range) => Some(range.end.line),
+            _ => None
+        }).fold(err.range.end.line, usize::max);
+        let space_to_border = get_digits_in_num(max_line) + 1; // + 1 because of the padding between the number and the border
+        let color = get_severity_color(err.severity);
         let cyan_wall = "┃".cyan();
-        let red_arrow = &format!("{}", "^".red());
-        for ind in err.range.start.line..=err.range.end.line {
-            let line_text = lines[ind as usize - 1];
-            let mut line = format!("{}{}{}   {}\n", ind, " ".repeat(space_to_border - get_digits_in_num(ind)), cyan_wall, line_text);
-            if ind == err.range.start.line {
-                let mut cols = format!("{} {}   ", " ".repeat(space_to_border - 1), cyan_wall);
-                if err.range.start.col == err.range.end.col && err.range.start.line == err.range.end.line {
-                    cols.push_str(&format!("{}{}", &" ".repeat(err.range.start.col), "^".red()));
-                } else {
-                for col in 0..=line_text.len() {
-                    if col >= err.range.start.col && col < err.range.end.col { cols.push_str(&red_arrow); }
-                    else { cols.push(' '); }
-                    }
-                }
-                cols.push('\n');
-                line.push_str(&cols);
+        let padding = " ".repeat(space_to_border - 1);
+        let numbered = |line: usize, text: &str| format!("{}{}{}   {}", line, " ".repeat(space_to_border - get_digits_in_num(line)), cyan_wall, text);
+        let title = if let Some(code) = err.code_str() { format!("{}[{}]:", err.severity, code) } else { format!("{}:", err.severity) };
+        let mut res = vec![format!("{} {} {} {}: {} {}", padding, "┎──".cyan(), filename, err.range, title.color(color).bold(), err.msg.color(color))];
+        let start_line = err.range.start.line.max(1);
+        let end_line = err.range.end.line.max(start_line);
+        for ind in start_line..=end_line {
+            // Long ranges only show their first and last lines
+            if end_line - start_line >= MAX_RANGE_LINES && ind >= start_line + MAX_RANGE_LINES - 1 && ind < end_line {
+                if ind == start_line + MAX_RANGE_LINES - 1 { res.push(format!("{} {}   ...", padding, cyan_wall)) };
+                continue;
             }
-            if ind == err.range.end.line && err.range.start.line != err.range.end.line {
-                let mut cols = format!("{} {}   ", " ".repeat(space_to_border - 1), cyan_wall);
-                for col in 0..=line_text.len() {
-                    if col >= err.range.end.col { cols.push_str(&red_arrow); }
-                    else { cols.push(' '); }
-                }
-                cols.push('\n');
-                line.push_str(&cols);
+            let line_text = get_line(&lines, ind);
+            let start_col = if ind == start_line { err.range.start.col } else { 0 };
+            let end_col = if ind == end_line { err.range.end.col } else { line_text.chars().count() };
+            let view = LineView::new(line_text, start_col);
+            res.push(numbered(ind, &view.text));
+            if ind == start_line || ind == end_line {
+                let (spaces, markers) = view.markers(start_col, end_col, "^");
+                res.push(format!("{} {}   {}{}", padding, cyan_wall, spaces, markers.color(color)));
             }
-        res.push_str(&line);
         }
         for label in &err.labels {
-            match label.variant {
+            match &label.variant {
                 ErrorLabelVariants::Help => {
-                    res.push_str(&format!("{}", format!("{} {}   Help: {}", " ".repeat(space_to_border - 1), cyan_wall, label.msg).bright_black()))
+                    res.push(format!("{} {}   {}", padding, cyan_wall, format!("Help: {}", label.msg).bright_black()));
                 }
-                ErrorLabelVariants::Sub(range) => {
-                    let line_in_question = lines[range.start.line - 1];
-                    let mut cols = format!("\n{} {}   ", " ".repeat(space_to_border - 1), cyan_wall);
-                    for col in 0..=line_in_question.len() {
-                        if col >= range.start.col && col < range.end.col { cols.push_str(&red_arrow); }
-                        else { cols.push(' '); }
+                ErrorLabelVariants::Suggestion(suggestion) => {
+                    res.push(format!("{} {}   {}", padding, cyan_wall, format!("Suggestion: {}", label.msg).bright_black()));
+                    let suggestion_lines = if suggestion.range.file() == err.range.file() { lines.clone() } else {
+                        split_lines(source_map.get_content(suggestion.range.file()).unwrap_or(""))
+                    };
+                    let (old, new) = get_suggestion_diff(&suggestion_lines, suggestion);
+                    for line in old {
+                        res.push(format!("{} {} {} {}", padding, cyan_wall, "-".red(), LineView::new(&line, suggestion.range.start.col).text.red()));
+                    }
+                    for line in new {
+                        res.push(format!("{} {} {} {}", padding, cyan_wall, "+".green(), LineView::new(&line, suggestion.range.start.col).text.green()));
                     }
-                    res.push_str(&format!("{}\n{} {}   {}{}", cyan_wall, cyan_wall, " ".repeat(space_to_border - 1), line_in_question, cols));
+                }
+                ErrorLabelVariants::Sub(range) => {
+                    // Secondary labels are marked with blue dashes, so they're distinguishable from the primary range
+                    let label_lines = if range.file() != err.range.file() {
+                        res.push(format!("{} {} {} {}", padding, "┠──".cyan(), source_map.get_name(range.file()).unwrap_or("<unknown>"), range));
+                        split_lines(source_map.get_content(range.file()).unwrap_or(""))
+                    } else {
+                        res.push(format!("{} {}", padding, cyan_wall));
+                        lines.clone()
+                    };
+                    let line_text = get_line(&label_lines, range.start.line);
+                    let end_col = if range.end.line == range.start.line { range.end.col } else { line_text.chars().count() };
+                    let view = LineView::new(line_text, range.start.col);
+                    let (spaces, dashes) = view.markers(range.start.col, end_col, "-");
+                    res.push(numbered(range.start.line.max(1), &view.text));
+                    res.push(format!("{} {}   {}{} {}", padding, cyan_wall, spaces, dashes.bright_blue(), label.msg.bright_blue()));
                 }
             }
         }
-        Some(res)
+        res.push(String::new());
+        Some(res.join("\n"))
+    }
+
+    // Formats the error as a single line of JSON
+    fn format_err_json(&self, err: &BaseError) -> String {
+        serde_json::to_string(&json::to_json_diagnostic(err, self.source_map())).unwrap()
+    }
+}
+
+impl ErrorFormatter for SourceMap {
+    fn source_map(&self) -> &SourceMap {
+        self
     }
 }
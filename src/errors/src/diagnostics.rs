@@ -1,185 +1,139 @@
+use crate::locale;
 
 pub struct Diagnostic {
     pub code: u16,
-    pub message: &'static str
+    pub name: &'static str,
+    // The names of the arguments of the message
+    pub args: &'static [&'static str]
 }
 
+impl Diagnostic {
+
+    // The message in the selected language, with the argument names in place of the values
+    pub fn message(&self) -> String {
+        let placeholders = self.args.iter().map(|arg| format!("{{{}}}", arg)).collect::<Vec<String>>();
+        let args = self.args.iter().copied().zip(placeholders.iter().map(|arg| arg.as_str())).collect::<Vec<(&str, &str)>>();
+        locale::get_message(self.code, &args)
+    }
+}
+
+// Every diagnostic also gets a function in the `messages` module, which builds its message.
+// The function takes exactly the arguments of the diagnostic. The messages themselves are in the catalogs in the `locales` folder.
 macro_rules! make_diagnostics {
-    ($([$name:ident, $code:expr, $msg:expr]),+) => {
-        $(
-            pub const $name: Diagnostic = Diagnostic {
-                code: $code,
-                message: $msg
-            };
-        )+
+    ($([$name:ident, $code:expr $(, $arg:ident)*]),+) => {
+        impl Diagnostics {
+            $(
+                pub const $name: Diagnostic = Diagnostic {
+                    code: $code,
+                    name: stringify!($name),
+                    args: &[$(stringify!($arg)),*]
+                };
+            )+
+
+            pub const ALL: &'static [Diagnostic] = &[$(Self::$name),+];
+        }
+
+        #[allow(non_snake_case)]
+        pub mod messages {
+            $(
+                pub fn $name($($arg: &str),*) -> String {
+                    $crate::locale::get_message($code, &[$((stringify!($arg), $arg)),*])
+                }
+            )+
+        }
     }
 }
 
 pub struct Diagnostics;
 
+make_diagnostics!(
+    [END_OF_STR, 1001],
+    [DECIMAL_POINT, 1002],
+    [EXPECTED_PROP_NAME, 1003],
+    [INVALID_CHAR, 1004, character],
+    [UNEXPECTED_OP, 1005, operator],
+    [UNEXPECTED_PUNC, 1006, punctuation],
+    [SEMICOLON, 1007],
+    [END_OF_BLOCK, 1008],
+    [EXPECTED_FOUND, 1009, expected, found],
+    [START_OF_BLOCK, 1010],
+    [ARROW_ACCESS, 1011],
+    [EXPECTED_DELIMITER, 1012, delimiter],
+    [TOO_MUCH_TYPES, 1013, expected],
+    [EMPTY_CHAR_LITERAL, 1014],
+    [CONST_WITHOUT_INIT, 1015],
+    [NO_GENERICS, 1016],
+    [END_OF_ITER, 1017],
+    [DISALLOWED, 1018, thing],
+    [MANY_ENTRIES, 1019],
+    [WRONG_MATCH_ARM_EXP, 1020],
+    [ALREADY_HAS_MODIFIER, 1021, modifier],
+    [CONFUSABLE, 1022, found, expected],
+    [INVALID_DIGIT, 1023],
+    [POINTLESS_TEMPLATE, 1024],
+    [ONE_CHAR_ENDPOINT, 1025],
+    [EXPECTED, 1026, expected],
+    [UNEXPECTED, 1027, found],
+    [UNEXPECTED_EOF, 1028],
+    [EMPTY_TYPE_PARAMS, 1029],
+    [REST_NOT_LAST, 1030],
+    [INVALID_FORMAT_SPEC, 1031, spec],
+    [TOO_DEEPLY_NESTED, 1032, max_depth],
+    [TYPE_NOT_FOUND_FROM_MOD, 2001, name, module],
+    [MOD_NOT_FOUND, 2002, module],
+    [DUPLICATE_IDENT, 2003, name],
+    [FILE_RECURSION, 2004, module, imported],
+    [NAME_NOT_FOUND, 2005, name],
+    [VAL_AS_TYPE, 2006],
+    [INVALID_AMOUNT_OF_TYPE_PARAMS, 2007, expected, found],
+    [DUPLICATE_FIELD, 2008, name],
+    [CANNOT_INFER, 2009, name],
+    [DUPLICATE_VARIANT, 2010, name],
+    [MISMATCHED_TYPES, 2011, expected, found],
+    [INVALID_AMOUNT_OF_VALUES, 2012, name, expected, found],
+    [CYCLIC_ALIAS, 2013, name, path],
+    [MISSING_BOUND_MEMBER, 2014, name, param, member],
+    [WRONG_BOUND_MEMBER, 2015, name, param, member, expected, found],
+    [MISSING_FIELD, 2016, found, expected, field],
+    [WRONG_FIELD_TYPE, 2017, found, expected, field],
+    [NOT_COMBINABLE, 2018, name],
+    [CONFLICTING_IMPL, 2019, partial, target],
+    [NOT_A_PARTIAL, 2020, name],
+    [INVALID_IMPL_TARGET, 2021, name],
+    [TYPE_AS_VALUE, 2022, name],
+    [INVALID_OPERANDS, 2023, op, left, right],
+    [INVALID_OPERAND, 2024, op, found],
+    [NO_MEMBER, 2025, name, target],
+    [INVALID_DECONSTRUCT, 2026, found],
+    [FORMAT_SPEC_MISMATCH, 2027, spec, found],
+    [MISSING_ARG, 2028, name],
+    [TOO_MANY_ARGS, 2029, expected, found],
+    [UNUSED_IMPORT, 3001, name],
+    [UNUSED_VARIABLE, 3002, name],
+    [SHADOWED_NAME, 3003, name],
+    [NON_SNAKE_CASE_FIELD, 3004, name, expected],
+    [NON_CAMEL_CASE_TYPE, 3005, name, expected],
+    [UNUSED_TYPE_PARAM, 3006, name],
+    [DEAD_CODE, 3007, name],
+    [SELF_ASSIGNMENT, 3008, name],
+    [UNKNOWN_LINT, 3009, name]
+);
+
 impl Diagnostics {
 
-    make_diagnostics!([
-        END_OF_STR,
-        1001,
-        "Expected end of string."
-    ], [
-        DECIMAL_POINT,
-        1002,
-        "Floating points cannot contain more than one decimal point."
-    ], [
-        EXPECTED_PROP_NAME,
-        1003,
-        "Expected a property name."
-    ], [
-        INVALID_CHAR,
-        1004,
-        "Invalid character $."
-    ], [
-        UNEXPECTED_OP,
-        1005,
-        "Unexpected operator $."
-    ], [
-        UNEXPECTED_PUNC,
-        1006,
-        "Unexpected punctuation $."
-    ], [
-        SEMICOLON,
-        1007,
-        "Expected semicolon at the end of the expression."
-    ], [
-        END_OF_BLOCK,
-        1008,
-        "Expected end of block."
-    ], [
-        EXPECTED_FOUND,
-        1009,
-        "Expected $, but found $."
-    ], [
-        START_OF_BLOCK,
-        1010,
-        "Expected start of block."
-    ], [
-        ARROW_ACCESS,
-        1011,
-        "Arrow access cannot be chained."
-    ], [
-        EXPECTED_DELIMITER,
-        1012,
-        "Expected delimiter $."
-    ], [
-        TOO_MUCH_TYPES,
-        1013,
-        "Too much typings provided, expected only $."
-    ], [
-        EMPTY_CHAR_LITERAL,
-        1014,
-        "Empty character literal."
-    ], [
-        CONST_WITHOUT_INIT,
-        1015,
-        "Constant variables must have an initializer."
-    ], [
-        NO_GENERICS,
-        1016,
-        "Generics are not allowed here."
-    ], [
-        END_OF_ITER,
-        1017,
-        "Expected end of iterator."
-    ], [
-        DISALLOWED,
-        1018,
-        "$ is not allowed here."
-    ], [
-        MANY_ENTRIES,
-        1019,
-        "Too many entry points. There can be only one."
-    ], [
-        WRONG_MATCH_ARM_EXP,
-        1020,
-        "Incorrect match arm expression. Match arms only accept enum variants or literals."
-    ], [
-        ALREADY_HAS_MODIFIER,
-        1021,
-        "The field is already $. Unnecessary modifier."
-    ], [
-        CONFUSABLE,
-        1022,
-        "Found $, which is very similar to $."
-    ], [
-        INVALID_DIGIT,
-        1023,
-        "Invalid digit."
-    ], [
-        POINTLESS_TEMPLATE,
-        1024,
-        "Pointless template literals. Use normal string literals instead."
-    ], [
-        ONE_CHAR_ENDPOINT,
-        1025,
-        "Expected character to contain only one codepoint."
-    ], [
-        EXPECTED,
-        1026,
-        "Expected $."
-    ], [
-        UNEXPECTED,
-        1027,
-        "Unexpected $."
-    ], [
-        UNEXPECTED_EOF,
-        1028,
-        "Unexpected end of file."
-    ], [
-        EMPTY_TYPE_PARAMS,
-        1029,
-        "Expected at least one type parameter."
-    ], [
-        TYPE_NOT_FOUND_FROM_MOD,
-        2001,
-        "Type $ not found from module $."
-    ], [
-        MOD_NOT_FOUND,
-        2002,
-        "Cannot find module \"$\"."
-    ], [
-        DUPLICATE_IDENT,
-        2003,
-        "Identifier \"$\" is already defined."
-    ], [
-        FILE_RECURSION,
-        2004,
-        "Detected recursive importing. Module \"$\" requires \"$\" and vice-versa."
-    ], [
-        NAME_NOT_FOUND,
-        2005,
-        "Couldn't find name $."
-    ], [
-        VAL_AS_TYPE,
-        2006,
-        "A value is being used as a type here."
-    ], [
-        INVALID_AMOUNT_OF_TYPE_PARAMS,
-        2007,
-        "Invalid amount of type parameters. Expected $, found $."
-    ]
-);
+    pub fn from_code(code: u16) -> Option<&'static Diagnostic> {
+        Self::ALL.iter().find(|diagnostic| diagnostic.code == code)
+    }
 
 }
 
-pub fn format_diagnostic(diagnostic: &Diagnostic, vars: Vec<&str>) -> String {
-    let msg = diagnostic.message;
-    if vars.is_empty() { return msg.to_string() };
-    let mut ind: usize = 0;
-    let mut new_str = String::new();
-    for ch in msg.chars() {
-        if ch == '$' {
-            new_str.push_str(&vars[ind]);
-            ind += 1;
-        } else {
-            new_str.push(ch)
-        }
-    }
-    new_str
+// Accepts codes in the `E1009`, `e1009` and `1009` formats
+pub fn parse_code(code: &str) -> Option<u16> {
+    let digits = code.strip_prefix(['E', 'e']).unwrap_or(code);
+    if !digits.starts_with(|ch: char| ch.is_ascii_digit()) { return None };
+    digits.parse().ok()
+}
+
+pub fn format_code(code: u16) -> String {
+    format!("E{:04}", code)
 }
\ No newline at end of file
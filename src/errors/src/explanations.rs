@@ -0,0 +1,992 @@
+
+// Long-form explanations for every diagnostic, shown by `lazy explain <code>`
+
+pub fn get_explanation(code: u16) -> Option<&'static str> {
+    Some(match code {
+        1001 => r#"A string literal was never closed.
+
+Every string which starts with a quote (") must end with another quote on the same file.
+
+Erroneous code example:
+
+    let greeting = "Hello World;
+
+Correct code:
+
+    let greeting = "Hello World";"#,
+        1002 => r#"A floating point literal contains more than one decimal point.
+
+Erroneous code example:
+
+    let pi = 3.14.15;
+
+Correct code:
+
+    let pi = 3.1415;"#,
+        1003 => r#"A property name was expected, but something else was found.
+
+Property names must be valid identifiers.
+
+Erroneous code example:
+
+    let person = new Person { 1: "Google" };
+
+Correct code:
+
+    let person = new Person { name: "Google" };"#,
+        1004 => r#"The source code contains a character which isn't part of Lazy's syntax.
+
+Characters like `@` and `$` can only be used inside string and character literals.
+
+Erroneous code example:
+
+    let email = me@example;
+
+Correct code:
+
+    let email = "me@example";"#,
+        1005 => r#"An operator was used in a place where an expression was expected.
+
+Only the `-`, `!`, `~`, `..`, `..=` and `...` operators can be at the start of an expression.
+
+Erroneous code example:
+
+    let a = * 5;
+
+Correct code:
+
+    let a = 5;"#,
+        1006 => r#"A punctuation character was used in a place where an expression was expected.
+
+Erroneous code example:
+
+    let a = );
+
+Correct code:
+
+    let a = ();"#,
+        1007 => r#"An expression inside a block is missing a semicolon.
+
+Every expression inside a block must end with a semicolon, unless it ends with a block itself (like `if`, `match` or `for`).
+
+Erroneous code example:
+
+    main {
+        let a = 5
+        print(a);
+    }
+
+Correct code:
+
+    main {
+        let a = 5;
+        print(a);
+    }"#,
+        1008 => r#"A block was never closed.
+
+Every block which starts with an opening curly bracket ({) must end with a closing one (}).
+
+Erroneous code example:
+
+    main {
+        print("Hello World");
+
+Correct code:
+
+    main {
+        print("Hello World");
+    }"#,
+        1009 => r#"The parser expected a specific token, but found a different one.
+
+The error message tells you what the parser was expecting, and what it found instead.
+
+Erroneous code example:
+
+    for i on 0..10 {
+        print(i);
+    }
+
+Correct code:
+
+    for i in 0..10 {
+        print(i);
+    }"#,
+        1010 => r#"A block was expected, but it's missing its opening curly bracket ({).
+
+Erroneous code example:
+
+    main
+        print("Hello World");
+    }
+
+Correct code:
+
+    main {
+        print("Hello World");
+    }"#,
+        1011 => r#"Arrow access expressions cannot be chained.
+
+Erroneous code example:
+
+    a->b->c;
+
+Correct code:
+
+    let b = a->b;
+    b->c;"#,
+        1012 => r#"A list is missing a delimiter between its elements.
+
+Erroneous code example:
+
+    let tuple = [1 2, 3];
+
+Correct code:
+
+    let tuple = [1, 2, 3];"#,
+        1013 => r#"More typings were provided than the declaration accepts.
+
+Erroneous code example:
+
+    static id<i32, str> = fn(value: i32) value;
+
+Correct code:
+
+    static id<i32> = fn(value: i32) value;"#,
+        1014 => r#"A character literal doesn't contain a character.
+
+Erroneous code example:
+
+    let ch = '';
+
+Correct code:
+
+    let ch = 'a';"#,
+        1015 => r#"A constant variable was declared without a value.
+
+Constant variables cannot be changed after they're declared, so they must be given a value right away.
+
+Erroneous code example:
+
+    const a;
+
+Correct code:
+
+    const a = 5;"#,
+        1016 => r#"Generics were provided in a place which doesn't accept them.
+
+Type parameters of `impl` blocks can only be plain names.
+
+Erroneous code example:
+
+    impl<T<i32>> Speak for Human {}
+
+Correct code:
+
+    impl<T> Speak for Human {}"#,
+        1017 => r#"An iterator is missing its end value.
+
+Erroneous code example:
+
+    for i in 0.. {
+        print(i);
+    }
+
+Correct code:
+
+    for i in 0..10 {
+        print(i);
+    }"#,
+        1018 => r#"A syntax feature was used in a place which doesn't allow it.
+
+For example, default values are only allowed in struct fields and function parameters, and spread parameters are only allowed in functions. Script files cannot have a main entry.
+
+Erroneous code example:
+
+    type Person = { name: str = "Google" };
+
+Correct code:
+
+    struct Person { name: str = "Google" }"#,
+        1019 => r#"A file contains more than one `main` entry.
+
+Every program has exactly one entry point.
+
+Erroneous code example:
+
+    main {
+        print("Hello");
+    }
+
+    main {
+        print("World");
+    }
+
+Correct code:
+
+    main {
+        print("Hello");
+        print("World");
+    }"#,
+        1020 => r#"A match arm contains an expression which isn't an enum variant or a literal.
+
+Match arms can only contain literals, natural tuples and iterators, enum variants, `none` and the `_` wildcard.
+
+Erroneous code example:
+
+    match value {
+        get_value() => print("Same!")
+    }
+
+Correct code:
+
+    match value {
+        5 => print("Same!"),
+        _ => print("Different!")
+    }"#,
+        1021 => r#"A modifier (`const`, `static` or `private`) was added to the same field more than once.
+
+Erroneous code example:
+
+    struct Person {
+        const const name: str
+    }
+
+Correct code:
+
+    struct Person {
+        const name: str
+    }"#,
+        1022 => r#"The source code contains a character which looks like a valid character, but is a different one.
+
+This usually happens when code is copied from a document or a website which replaces punctuation with similar-looking unicode characters.
+
+Erroneous code example (the semicolon is a greek question mark):
+
+    let a = 5;
+
+Correct code:
+
+    let a = 5;"#,
+        1023 => r#"A number literal contains a digit which isn't valid for its base.
+
+Binary literals (0b) can only contain 0 and 1, octal literals (0o) only 0 to 7, and decimal literals cannot contain letters.
+
+Erroneous code example:
+
+    let mask = 0b1021;
+
+Correct code:
+
+    let mask = 0b1011;"#,
+        1024 => r#"A template literal doesn't contain any interpolations.
+
+Template literals are only useful when they contain values (${...}). Use a normal string literal instead. This is a warning, so it doesn't stop the code from compiling.
+
+Erroneous code example:
+
+    let greeting = `Hello World`;
+
+Correct code:
+
+    let greeting = "Hello World";"#,
+        1025 => r#"A character literal contains more than one character.
+
+Use a string literal if you need more than one character.
+
+Erroneous code example:
+
+    let letters = 'ab';
+
+Correct code:
+
+    let letters = "ab";"#,
+        1026 => r#"The parser expected something which is missing.
+
+The error message tells you what was expected.
+
+Erroneous code example:
+
+    let a = ;
+
+Correct code:
+
+    let a = 5;"#,
+        1027 => r#"The parser found something it didn't expect in this position.
+
+Erroneous code example:
+
+    if value {
+        yield 5;
+    }
+
+Correct code:
+
+    for value in values {
+        yield value;
+    }"#,
+        1028 => r#"The file ended in the middle of an expression or a statement.
+
+Erroneous code example:
+
+    main {
+        let a = 1 +
+
+Correct code:
+
+    main {
+        let a = 1 + 2;
+    }"#,
+        1029 => r#"A type parameter list is empty.
+
+Erroneous code example:
+
+    struct Wrapper<> {
+        value: i32
+    }
+
+Correct code:
+
+    struct Wrapper<T> {
+        value: T
+    }"#,
+        1030 => r#"A rest element (`...name`) is not the last element of a deconstruct pattern.
+
+The rest element collects all remaining elements, so nothing can come after it.
+
+Erroneous code example:
+
+    let [...rest, last] = [1, 2, 3];
+
+Correct code:
+
+    let [first, ...rest] = [1, 2, 3];"#,
+        1031 => r#"A template literal interpolation contains an invalid format specifier.
+
+Format specifiers have the form [[fill]align][+][#][0][width][.precision][kind], where align is one of <, > or ^, and kind is one of x, X, b, o, e, E or ?.
+
+Erroneous code example:
+
+    `${price:8.x2}`;
+
+Correct code:
+
+    `${price:>8.2}`;"#,
+        1032 => r#"The code is nested too deeply.
+
+To protect the compiler from crashing, expressions, types and deconstruct patterns can only be nested up to a certain depth. Only real nesting counts - long chains of operators or calls are fine. Split the code into multiple variables.
+
+Erroneous code example:
+
+    let a = ((((((((((((((((((((((((((((1)))))))))))))))))))))))))))); // ...and so on
+
+Correct code:
+
+    let inner = (((1)));
+    let a = (((inner)));"#,
+        2001 => r#"An item which is being imported isn't exported from the module.
+
+Only exported items can be imported from other modules.
+
+Erroneous code example:
+
+    // songs.lazy
+    struct Song {}
+
+    // main.lazy
+    import { Song } from "./songs"
+
+Correct code:
+
+    // songs.lazy
+    export struct Song {}
+
+    // main.lazy
+    import { Song } from "./songs""#,
+        2002 => r#"The module in an import statement couldn't be found.
+
+Paths are relative to the file which contains the import. The `.lazy` extension can be omitted.
+
+Erroneous code example:
+
+    import * from "./sogns"
+
+Correct code:
+
+    import * from "./songs""#,
+        2003 => r#"The same name was declared more than once in the same module.
+
+Erroneous code example:
+
+    struct Song {}
+    enum Song {}
+
+Correct code:
+
+    struct Song {}
+    enum SongGenre {}"#,
+        2004 => r#"Two modules import each other.
+
+Erroneous code example:
+
+    // a.lazy
+    import * from "./b"
+
+    // b.lazy
+    import * from "./a"
+
+Correct code:
+
+    // a.lazy
+    import * from "./b"
+
+    // b.lazy
+    export type A = {}"#,
+        2005 => r#"A name was used, but it's not declared or imported.
+
+Erroneous code example:
+
+    struct Playlist {
+        songs: Vec<Sogn>
+    }
+
+Correct code:
+
+    struct Playlist {
+        songs: Vec<Song>
+    }"#,
+        2006 => r#"A value (for example an enum variant) was used in a place where a type is expected.
+
+Erroneous code example:
+
+    enum Genre { Rock, Pop }
+
+    struct Song {
+        genre: Genre::Rock
+    }
+
+Correct code:
+
+    enum Genre { Rock, Pop }
+
+    struct Song {
+        genre: Genre
+    }"#,
+        2007 => r#"A generic type was given the wrong amount of type parameters.
+
+Erroneous code example:
+
+    struct Pair<K, V> {
+        key: K,
+        value: V
+    }
+
+    type Entry = Pair<str>;
+
+Correct code:
+
+    struct Pair<K, V> {
+        key: K,
+        value: V
+    }
+
+    type Entry = Pair<str, i32>;"#,
+        2008 => r#"A struct has more than one field with the same name.
+
+Erroneous code example:
+
+    struct Song {
+        name: str,
+        name: str
+    }
+
+Correct code:
+
+    struct Song {
+        name: str,
+        artist: str
+    }"#,
+        2009 => r#"The type of a field, a parameter or a variable couldn't be inferred from its value.
+
+Fields, parameters, variables and statics without a type annotation get the type of their value. Fields and parameters can only infer the type from literals, tuples, `new` expressions and functions with a return type. A static cannot refer to itself in its own value, and a `for` loop needs a range or a value with a `next` member.
+
+Erroneous code example:
+
+    struct Counter {
+        count = get_start()
+    }
+
+Correct code:
+
+    struct Counter {
+        count: i32 = get_start()
+    }"#,
+        2010 => r#"An enum has more than one variant with the same name.
+
+Erroneous code example:
+
+    enum Shape {
+        Circle: f32,
+        Circle: i32
+    }
+
+Correct code:
+
+    enum Shape {
+        Circle: f32,
+        Square: f32
+    }"#,
+        2011 => r#"A value doesn't have the type which is expected in its place.
+
+Erroneous code example:
+
+    enum Token {
+        Number: i32
+    }
+
+    struct Parser {
+        last = Token::Number("5")
+    }
+
+Correct code:
+
+    struct Parser {
+        last = Token::Number(5)
+    }"#,
+        2012 => r#"An enum variant is constructed with the wrong amount of values. Variants with a type take exactly one value, and variants without a type don't take any.
+
+Erroneous code example:
+
+    enum Token {
+        Number: i32,
+        Invalid
+    }
+
+    struct Parser {
+        last = Token::Invalid(5)
+    }
+
+Correct code:
+
+    struct Parser {
+        last = Token::Invalid
+    }"#,
+        2013 => r#"A type alias refers to itself, directly or through other aliases. Aliases are replaced with the type they alias, so a cyclic alias would never end.
+
+Erroneous code example:
+
+    type List = { value: i32, next: List? }
+
+Correct code:
+
+    struct List {
+        value: i32,
+        next: List?
+    }"#,
+        2014 => r#"A type argument doesn't have a member which is required by the constraint of its type parameter.
+
+Erroneous code example:
+
+    struct Error<T: { to_string: () -> str }> {
+        value: T
+    }
+
+    struct Parser {
+        last: Error<i32>
+    }
+
+Correct code:
+
+    struct Code {
+        to_string: () -> str
+    }
+
+    struct Parser {
+        last: Error<Code>
+    }"#,
+        2015 => r#"A member of a type argument has a different type than the one required by the constraint of its type parameter.
+
+Erroneous code example:
+
+    struct Error<T: { to_string: () -> str }> {
+        value: T
+    }
+
+    struct Code {
+        to_string: () -> i32
+    }
+
+    struct Parser {
+        last: Error<Code>
+    }
+
+Correct code:
+
+    struct Code {
+        to_string: () -> str
+    }"#,
+        2016 => r#"A struct is used as a partial, but it doesn't have one of the fields of the partial. Partials are types which are written as a list of fields, any struct which has all of the fields can be used as the partial.
+
+Erroneous code example:
+
+    type WithName = { name: str }
+
+    struct Song {
+        title: str
+    }
+
+    struct Playlist {
+        first: WithName = new Song { title: "Bohemian Rhapsody" }
+    }
+
+Correct code:
+
+    struct Song {
+        name: str
+    }
+
+    struct Playlist {
+        first: WithName = new Song { name: "Bohemian Rhapsody" }
+    }"#,
+        2017 => r#"A struct is used as a partial, but one of its fields has a different type than the field of the partial.
+
+Erroneous code example:
+
+    type WithName = { name: str }
+
+    struct Song {
+        name: i32
+    }
+
+    struct Playlist {
+        first: WithName = new Song { name: 1 }
+    }
+
+Correct code:
+
+    struct Song {
+        name: str
+    }
+
+    struct Playlist {
+        first: WithName = new Song { name: "Bohemian Rhapsody" }
+    }"#,
+        2018 => r#"Only types with fields, like structs and partials, can be combined with the `+` operator.
+
+Erroneous code example:
+
+    type Named = { name: str }
+
+    type NamedNumber = Named + i32
+
+Correct code:
+
+    type Numbered = { number: i32 }
+
+    type NamedNumber = Named + Numbered"#,
+        2019 => r#"The same partial is implemented more than once for the same type. The implementations can be in different modules.
+
+An implementation for a generic type, like `impl<T> Show for Vec<T>`, conflicts with every implementation of the partial for an instance of the type, like `impl Show for Vec<str>`.
+
+Erroneous code example:
+
+    type Speak = { speak: () -> str }
+
+    impl Speak for Human {
+        speak: fn() -> str "Hello"
+    }
+
+    impl Speak for Human {
+        speak: fn() -> str "Hi"
+    }
+
+Correct code:
+
+    impl Speak for Human {
+        speak: fn() -> str "Hello"
+    }"#,
+        2020 => r#"The type before `for` in an impl block isn't a partial. Only partials, which are types written as a list of fields, can be implemented.
+
+Erroneous code example:
+
+    impl i32 for Human {}
+
+Correct code:
+
+    type Speak = { speak: () -> str }
+
+    impl Speak for Human {
+        speak: fn() -> str "Hello"
+    }"#,
+        2021 => r#"A partial is implemented for a type which isn't a struct or an enum.
+
+Erroneous code example:
+
+    type Speak = { speak: () -> str }
+
+    impl Speak for i32 {
+        speak: fn() -> str "Hello"
+    }
+
+Correct code:
+
+    struct Number {
+        value: i32
+    }
+
+    impl Speak for Number {
+        speak: fn() -> str "Hello"
+    }"#,
+        2022 => r#"A type is used where a value is expected.
+
+Structs, enums, type aliases and type parameters are types. Values are variables, parameters and statics.
+
+Erroneous code example:
+
+    struct Point {
+        x: i32
+    }
+
+    main {
+        let point = Point
+    }
+
+Correct code:
+
+    main {
+        let point = new Point { x: 1 }
+    }"#,
+        2023 => r#"A binary operator is used with operands it doesn't support.
+
+Arithmetic operators need two numbers of the same type, `+` also joins two strings. Comparisons need numbers or characters, `&&` and `||` need booleans, and `==` needs operands of compatible types.
+
+Erroneous code example:
+
+    main {
+        let value = 1 + "a"
+    }
+
+Correct code:
+
+    main {
+        let value = 1 + 2
+    }"#,
+        2024 => r#"A unary operator is used with an operand it doesn't support.
+
+`!` needs a boolean, `-` needs a number and `~` needs an integer. Only functions can be called.
+
+Erroneous code example:
+
+    main {
+        let value = !1
+    }
+
+Correct code:
+
+    main {
+        let value = !true
+    }"#,
+        2025 => r#"A member is accessed on a type which doesn't have it.
+
+Erroneous code example:
+
+    struct Point {
+        x: i32
+    }
+
+    main {
+        let point = new Point { x: 1 }
+        let y = point.y
+    }
+
+Correct code:
+
+    main {
+        let point = new Point { x: 1 }
+        let x = point.x
+    }"#,
+        2026 => r#"A value is deconstructed with a pattern which doesn't fit its type.
+
+Tuple patterns need a tuple with at least as many elements as the pattern, and struct patterns need a value with fields. Optional values must be unwrapped first.
+
+Erroneous code example:
+
+    main {
+        let [a, b, c] = [1, 2]
+    }
+
+Correct code:
+
+    main {
+        let [a, b] = [1, 2]
+    }"#,
+        2027 => r#"A format spec in a template string is used with a value it doesn't support.
+
+`?` formats any value. `x`, `X`, `b` and `o` need integers, `e` and `E` need numbers, signs and zero padding need numbers, and a precision needs a number or a string.
+
+Erroneous code example:
+
+    main {
+        let hex = `{"a":x}`
+    }
+
+Correct code:
+
+    main {
+        let hex = `{255:x}`
+    }"#,
+        2028 => r#"A function is called without a value for one of its parameters.
+
+Only parameters which are optional, have a default value or collect the rest of the arguments can be left out.
+
+Erroneous code example:
+
+    main {
+        let add = fn(a: i32, b: i32) -> i32 a + b;
+        add(1);
+    }
+
+Correct code:
+
+    main {
+        let add = fn(a: i32, b = 0) -> i32 a + b;
+        add(1);
+    }"#,
+        2029 => r#"A function is called with more arguments than it has parameters.
+
+Erroneous code example:
+
+    main {
+        let double = fn(a: i32) -> i32 a * 2;
+        double(1, 2);
+    }
+
+Correct code:
+
+    main {
+        let double = fn(a: i32) -> i32 a * 2;
+        double(1);
+    }"#,
+        3001 => r#"An imported name is never used in the module. This is the `unused_imports` lint.
+
+Erroneous code example:
+
+    import { Song, Playlist } from "./songs"
+
+    export type Album = Vec<Song>;
+
+Correct code:
+
+    import { Song } from "./songs"
+
+    export type Album = Vec<Song>;"#,
+        3002 => r#"A variable or a parameter is never used. This is the `unused_variables` lint.
+
+Names which start with an underscore are never reported.
+
+Erroneous code example:
+
+    main {
+        let count = 5;
+    }
+
+Correct code:
+
+    main {
+        let _count = 5;
+    }"#,
+        3003 => r#"A variable has the same name as a variable from an outer scope, so the outer one cannot be used anymore. This is the `shadowed_names` lint.
+
+Erroneous code example:
+
+    main {
+        let value = 5;
+        if true {
+            let value = 10;
+            print(value);
+        }
+    }
+
+Correct code:
+
+    main {
+        let value = 5;
+        if true {
+            let other = 10;
+            print(other);
+        }
+    }"#,
+        3004 => r#"A struct field doesn't have a snake case name. This is the `non_snake_case_fields` lint.
+
+Erroneous code example:
+
+    struct Song {
+        releaseDate: str
+    }
+
+Correct code:
+
+    struct Song {
+        release_date: str
+    }"#,
+        3005 => r#"A struct, enum, enum variant or type alias doesn't have an upper camel case name. This is the `non_camel_case_types` lint.
+
+Erroneous code example:
+
+    struct song_info {}
+
+Correct code:
+
+    struct SongInfo {}"#,
+        3006 => r#"A type parameter is declared, but never used. This is the `unused_type_params` lint.
+
+Erroneous code example:
+
+    struct Wrapper<T, U> {
+        value: T
+    }
+
+Correct code:
+
+    struct Wrapper<T> {
+        value: T
+    }"#,
+        3007 => r#"A struct, enum or type alias which isn't exported is never used. This is the `dead_code` lint.
+
+Erroneous code example:
+
+    struct Helper {}
+
+    export struct Song {}
+
+Correct code:
+
+    export struct Song {}"#,
+        3008 => r#"A variable is assigned to itself, which doesn't do anything. This is the `self_assignment` lint.
+
+Erroneous code example:
+
+    main {
+        let a = 5;
+        a = a;
+    }
+
+Correct code:
+
+    main {
+        let a = 5;
+        a = 6;
+    }"#,
+        3009 => r#"An `#allow`, `#warn` or `#deny` attribute names a lint which doesn't exist. This is the `unknown_lints` lint.
+
+Erroneous code example:
+
+    #allow(unused_varibles)
+    struct Song {}
+
+Correct code:
+
+    #allow(unused_variables)
+    struct Song {}"#,
+        _ => return None
+    })
+}
@@ -0,0 +1,61 @@
+use super::*;
+
+// Applies the suggestions to the text. Suggestions which overlap with an already applied one are skipped.
+// Returns the new text and the number of applied suggestions.
+pub fn apply_suggestions(content: &str, suggestions: &[&ErrorSuggestion]) -> (String, usize) {
+    let mut sorted = suggestions.to_vec();
+    sorted.sort_by_key(|suggestion| (suggestion.range.start.pos, suggestion.range.end.pos));
+    let chars: Vec<char> = content.chars().collect();
+    let mut res = String::with_capacity(content.len());
+    let mut last_pos = 0;
+    let mut applied = 0;
+    for suggestion in sorted {
+        let start = suggestion.range.start.pos;
+        let end = suggestion.range.end.pos;
+        if start < last_pos || end < start || end > chars.len() { continue };
+        res.extend(&chars[last_pos..start]);
+        res.push_str(&suggestion.replacement);
+        last_pos = end;
+        applied += 1;
+    }
+    res.extend(&chars[last_pos..]);
+    (res, applied)
+}
+
+// Applies all machine-applicable suggestions of the errors which are inside the file
+pub fn apply_machine_applicable(content: &str, file: FileId, errors: &[BaseError]) -> (String, usize) {
+    let suggestions = errors.iter()
+        .flat_map(|err| err.suggestions())
+        .filter(|suggestion| suggestion.applicability == Applicability::MachineApplicable && suggestion.range.file() == file)
+        .collect::<Vec<&ErrorSuggestion>>();
+    apply_suggestions(content, &suggestions)
+}
+
+// Every pass can uncover new errors, because the parser stops at the first error in a statement
+pub const MAX_FIX_PASSES: usize = 16;
+
+// Applies all machine-applicable suggestions, until there are no more left. `check` checks the files from scratch and
+// returns the diagnostics together with the sources they point to, `write` saves the new content of a file.
+// Returns the number of applied suggestions.
+pub fn apply_until_fixed<F: builder::ErrorFormatter, E>(mut check: impl FnMut() -> (F, ErrorCollector), mut write: impl FnMut(&str, String) -> Result<(), E>) -> Result<usize, E> {
+    let mut total = 0;
+    for _ in 0..MAX_FIX_PASSES {
+        let (formatter, errors) = check();
+        let source_map = formatter.source_map();
+        // The suggestions can be in any of the imported files
+        let mut fixed_files: Vec<FileId> = errors.collected.iter().flat_map(|err| err.suggestions()).map(|suggestion| suggestion.range.file()).collect();
+        fixed_files.sort_by_key(|file| file.0);
+        fixed_files.dedup();
+        let mut applied_in_pass = 0;
+        for file in fixed_files {
+            let source = if let Some(source) = source_map.get(file) { source } else { continue };
+            let (fixed, applied) = apply_machine_applicable(&source.content, file, &errors.collected);
+            if applied == 0 { continue };
+            write(&source.name, fixed)?;
+            applied_in_pass += applied;
+        }
+        if applied_in_pass == 0 { break };
+        total += applied_in_pass;
+    }
+    Ok(total)
+}
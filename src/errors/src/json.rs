@@ -0,0 +1,104 @@
+use serde::Serialize;
+use super::*;
+
+// The schema of the JSON output. Fields are only ever added to it, never removed or renamed.
+// See the "JSON output" section of the CLI readme for the full description.
+
+#[derive(Serialize)]
+pub struct JsonPosition {
+    pub line: usize,
+    pub column: usize,
+    pub byte: usize
+}
+
+#[derive(Serialize)]
+pub struct JsonRange {
+    pub start: JsonPosition,
+    pub end: JsonPosition
+}
+
+#[derive(Serialize)]
+pub struct JsonLabel {
+    pub kind: &'static str,
+    pub message: String,
+    pub file: String,
+    pub range: Option<JsonRange>
+}
+
+#[derive(Serialize)]
+pub struct JsonFix {
+    pub message: String,
+    pub replacement: String,
+    pub file: String,
+    pub range: JsonRange,
+    pub applicability: &'static str
+}
+
+#[derive(Serialize)]
+pub struct JsonDiagnostic {
+    pub code: Option<String>,
+    pub severity: &'static str,
+    pub message: String,
+    pub file: String,
+    pub range: JsonRange,
+    pub labels: Vec<JsonLabel>,
+    pub fixes: Vec<JsonFix>
+}
+
+pub fn severity_to_str(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note"
+    }
+}
+
+// Lines and columns are 1-based, byte offsets are 0-based. Ends are exclusive.
+fn to_json_position(loc: &LoC, content: Option<&str>) -> JsonPosition {
+    let byte = match content {
+        Some(text) => text.char_indices().nth(loc.pos).map(|(ind, _)| ind).unwrap_or(text.len()),
+        None => loc.pos
+    };
+    JsonPosition { line: loc.line, column: loc.col + 1, byte }
+}
+
+pub fn to_json_range(range: &Range, content: Option<&str>) -> JsonRange {
+    JsonRange {
+        start: to_json_position(&range.start, content),
+        end: to_json_position(&range.end, content)
+    }
+}
+
+pub fn to_json_diagnostic(err: &BaseError, source_map: &SourceMap) -> JsonDiagnostic {
+    let content = source_map.get_content(err.range.file());
+    JsonDiagnostic {
+        code: err.code_str(),
+        severity: severity_to_str(err.severity),
+        message: err.msg.clone(),
+        file: source_map.get_name(err.range.file()).unwrap_or_default().to_string(),
+        range: to_json_range(&err.range, content),
+        labels: err.labels.iter().filter_map(|label| match &label.variant {
+            ErrorLabelVariants::Help => Some(JsonLabel { kind: "help", message: label.msg.clone(), file: source_map.get_name(err.range.file()).unwrap_or_default().to_string(), range: None }),
+            ErrorLabelVariants::Sub(range) => Some(JsonLabel {
+                kind: "secondary",
+                message: label.msg.clone(),
+                file: source_map.get_name(range.file()).unwrap_or_default().to_string(),
+                range: Some(to_json_range(range, source_map.get_content(range.file())))
+            }),
+            ErrorLabelVariants::Suggestion(_) => None
+        }).collect(),
+        fixes: err.labels.iter().filter_map(|label| match &label.variant {
+            ErrorLabelVariants::Suggestion(suggestion) => Some(JsonFix {
+                message: label.msg.clone(),
+                replacement: suggestion.replacement.clone(),
+                file: source_map.get_name(suggestion.range.file()).unwrap_or_default().to_string(),
+                range: to_json_range(&suggestion.range, source_map.get_content(suggestion.range.file())),
+                applicability: match suggestion.applicability {
+                    Applicability::MachineApplicable => "machine-applicable",
+                    Applicability::MaybeIncorrect => "maybe-incorrect"
+                }
+            }),
+            _ => None
+        }).collect()
+    }
+}
@@ -1,15 +1,28 @@
 pub mod builder;
 pub mod diagnostics;
+pub mod explanations;
+pub mod json;
+pub mod sarif;
+pub mod fix;
+pub mod source_map;
+pub mod locale;
 use std::fmt;
+use std::collections::HashSet;
 
 pub use diagnostics::*;
 pub use builder::*;
+pub use source_map::SourceMap;
+
+// Identifies a file inside a `SourceMap`. The default id doesn't belong to any file.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug)]
+pub struct FileId(pub u32);
 
 #[derive(Copy, Default)]
 pub struct LoC {
     pub line: usize,
     pub col: usize,
-    pub pos: usize
+    pub pos: usize,
+    pub file: FileId
 }
 
 impl std::clone::Clone for LoC {
@@ -55,17 +68,72 @@ impl fmt::Display for Range {
 
 impl Range {
 
+    pub fn file(&self) -> FileId {
+        self.start.file
+    }
+
+    // Empty ranges are treated as if they contain the character after them
+    pub fn overlaps(&self, other: &Range) -> bool {
+        self.file() == other.file()
+            && self.start.pos < other.end.pos.max(other.start.pos + 1)
+            && other.start.pos < self.end.pos.max(self.start.pos + 1)
+    }
+
     pub fn end_with(self, end: &LoC) -> Range {
         Range { start: self.start, end: end.clone() }
     }
 
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Error => "Error",
+            Self::Warning => "Warning",
+            Self::Note => "Note"
+        })
+    }
+}
+
+// How safe it is to apply a suggestion without a human looking at it
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Applicability {
+    MachineApplicable,
+    MaybeIncorrect
+}
+
+impl fmt::Display for Applicability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::MachineApplicable => "machine-applicable",
+            Self::MaybeIncorrect => "maybe-incorrect"
+        })
+    }
+}
+
+// Replaces the text in the range with the replacement. An empty range inserts the replacement.
+#[derive(Clone)]
+pub struct ErrorSuggestion {
+    pub replacement: String,
+    pub range: Range,
+    pub applicability: Applicability
+}
+
+#[derive(Clone)]
 pub enum ErrorLabelVariants {
     Help,
-    Sub(Range)
+    Sub(Range),
+    Suggestion(ErrorSuggestion)
 }
 
+#[derive(Clone)]
 pub struct ErrorLabel {
     pub msg: String,
     pub variant: ErrorLabelVariants
@@ -77,9 +145,12 @@ pub struct ErrorLabel {
     - Be between the main error range
 */
 
+#[derive(Clone)]
 pub struct BaseError {
     pub range: Range,
     pub msg: String,
+    pub code: Option<u16>,
+    pub severity: Severity,
     pub labels: Vec<ErrorLabel>
 }
 
@@ -89,6 +160,8 @@ impl BaseError {
         Self {
             msg,
             range: range,
+            code: None,
+            severity: Severity::Error,
             labels: vec![]
         }
     }
@@ -97,15 +170,68 @@ impl BaseError {
         Self {
             msg,
             range: range,
+            code: None,
+            severity: Severity::Error,
             labels
         }
     }
 
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    pub fn with_suggestion(mut self, msg: &str, range: Range, replacement: &str, applicability: Applicability) -> Self {
+        self.labels.push(ErrorLabel {
+            msg: msg.to_string(),
+            variant: ErrorLabelVariants::Suggestion(ErrorSuggestion { replacement: replacement.to_string(), range, applicability })
+        });
+        self
+    }
+
+    // Adds a secondary label. The range can be in a different file than the error.
+    pub fn with_label(mut self, msg: &str, range: Range) -> Self {
+        self.labels.push(ErrorLabel {
+            msg: msg.to_string(),
+            variant: ErrorLabelVariants::Sub(range)
+        });
+        self
+    }
+
+    pub fn suggestions(&self) -> impl Iterator<Item = &ErrorSuggestion> {
+        self.labels.iter().filter_map(|label| match &label.variant {
+            ErrorLabelVariants::Suggestion(suggestion) => Some(suggestion),
+            _ => None
+        })
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    pub fn is_syntax_error(&self) -> bool {
+        self.is_error() && matches!(self.code, Some(1000..=1999))
+    }
+
+    // Invalid characters and diagnostics which can be fixed automatically are more likely to be the cause of the
+    // errors around them than to be caused by them
+    pub fn is_root_cause(&self) -> bool {
+        matches!(self.code, Some(code) if code == Diagnostics::INVALID_CHAR.code || code == Diagnostics::CONFUSABLE.code)
+            || self.suggestions().any(|suggestion| suggestion.applicability == Applicability::MachineApplicable)
+    }
+
+    // The code in the `E1009` format
+    pub fn code_str(&self) -> Option<String> {
+        Some(format_code(self.code?))
+    }
+
 }
 
 impl fmt::Debug for BaseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Error")
+         .field("code", &self.code_str())
+         .field("severity", &self.severity)
          .field("msg", &self.msg.to_string())
          .field("range", &self.range.to_string())
          .finish()
@@ -114,7 +240,10 @@ impl fmt::Debug for BaseError {
 
 impl fmt::Display for BaseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "Error: {}", self.msg.to_string())
+        match self.code_str() {
+            Some(code) => writeln!(f, "{}[{}]: {}", self.severity, code, self.msg),
+            None => writeln!(f, "{}: {}", self.severity, self.msg)
+        }
     }
 }
 
@@ -125,14 +254,22 @@ pub type LazyMultiResult<T> = Result<T, ErrorCollector>;
 
 pub struct ErrorCollector {
     pub collected: Vec<BaseError>,
-    pub filename: String 
+    // The file which is being parsed
+    pub file: FileId,
+    // How many errors were removed by `truncate_errors`
+    pub hidden: usize
 } 
 
+fn plural(count: usize, word: &str) -> String {
+    format!("{} {}{}", count, word, if count == 1 { "" } else { "s" })
+}
+
 impl ErrorCollector {
-    pub fn new(filename: &str) -> Self {
+    pub fn new(file: FileId) -> Self {
         ErrorCollector {
             collected: vec![],
-            filename: filename.to_string()
+            file,
+            hidden: 0
         }
     }
 
@@ -140,14 +277,78 @@ impl ErrorCollector {
         self.collected.push(err);
     }
 
+    // Warnings and notes don't prevent a module from being compiled
+    pub fn has_errors(&self) -> bool {
+        self.collected.iter().any(|err| err.is_error())
+    }
+
+    pub fn count(&self, severity: Severity) -> usize {
+        self.collected.iter().filter(|err| err.severity == severity).count()
+    }
+
+    // Removes the warnings and notes with the allowed codes, and turns the rest of the warnings into errors if `deny_warnings` is set
+    pub fn apply_levels(&mut self, deny_warnings: bool, allowed: &[u16]) {
+        self.collected.retain(|err| err.is_error() || !matches!(err.code, Some(code) if allowed.contains(&code)));
+        if deny_warnings {
+            for err in &mut self.collected {
+                if err.severity == Severity::Warning { err.severity = Severity::Error };
+            }
+        }
+    }
+
+    // Sorts the diagnostics by file and position, removes the duplicates (same range and code), and removes the diagnostics which
+    // overlap with an earlier syntax error, because they're most likely caused by it. Root causes are always kept.
+    pub fn normalize(&mut self) {
+        self.collected.sort_by_key(|err| (err.range.file().0, err.range.start.pos, err.range.end.pos));
+        let mut seen: HashSet<(FileId, usize, usize, Option<u16>)> = HashSet::new();
+        // The furthest end of the kept syntax errors in the current file. Every kept syntax error starts before the current diagnostic.
+        let mut syntax_end: Option<(FileId, usize)> = None;
+        self.collected.retain(|err| {
+            let range = err.range;
+            let is_cascade = matches!(syntax_end, Some((file, end)) if file == range.file() && range.start.pos < end) && !err.is_root_cause();
+            if is_cascade || !seen.insert((range.file(), range.start.pos, range.end.pos, err.code)) { return false };
+            if err.is_syntax_error() {
+                let end = range.end.pos.max(range.start.pos + 1);
+                syntax_end = Some((range.file(), syntax_end.filter(|(file, _)| *file == range.file()).map_or(end, |(_, other)| other.max(end))));
+            }
+            true
+        });
+    }
+
+    // Keeps the first `limit` errors. Warnings and notes after the last kept error are removed too.
+    pub fn truncate_errors(&mut self, limit: usize) {
+        let total = self.count(Severity::Error);
+        if total <= limit { return };
+        let cut = if limit == 0 { 0 } else {
+            self.collected.iter().enumerate().filter(|(_, err)| err.is_error()).nth(limit - 1).map_or(self.collected.len(), |(ind, _)| ind + 1)
+        };
+        self.collected.truncate(cut);
+        self.hidden += total - limit;
+    }
+
+    // A line like "Found 3 errors and 1 warning.", or None if there's nothing to report
+    pub fn summary(&self) -> Option<String> {
+        let errors = self.count(Severity::Error) + self.hidden;
+        let counts = [(errors, "error"), (self.count(Severity::Warning), "warning"), (self.count(Severity::Note), "note")]
+            .iter().filter(|(count, _)| *count != 0).map(|(count, word)| plural(*count, word)).collect::<Vec<String>>();
+        let (last, rest) = counts.split_last()?;
+        let mut summary = if rest.is_empty() { format!("Found {}.", last) } else { format!("Found {} and {}.", rest.join(", "), last) };
+        if self.hidden != 0 {
+            summary.push_str(&format!(" {} not shown because of the error limit.", if self.hidden == 1 { String::from("1 error is") } else { format!("{} errors are", self.hidden) }));
+        }
+        Some(summary)
+    }
+
 }
 
 #[macro_export]
 macro_rules! err {
     ($diagnostic: ident, $range: expr, $($vars: expr),*; $([$label_text: expr, $label_range: expr]),*) => {
             BaseError {
-                msg: format_diagnostic(&Diagnostics::$diagnostic, vec![$($vars),*]),
+                msg: $crate::diagnostics::messages::$diagnostic($($vars),*),
                 range: $range,
+                code: Some(Diagnostics::$diagnostic.code),
+                severity: Severity::Error,
                 labels: vec![$(
                     ErrorLabel {
                         msg: String::from($label_text),
@@ -158,27 +359,33 @@ macro_rules! err {
     };
     ($diagnostic: ident, $range: expr, $($vars: expr),*; $([$label_text: expr]),*) => {
         BaseError {
-            msg: format_diagnostic(&Diagnostics::$diagnostic, vec![$($vars),*]),
+            msg: $crate::diagnostics::messages::$diagnostic($($vars),*),
             range: $range,
+            code: Some(Diagnostics::$diagnostic.code),
+            severity: Severity::Error,
             labels: vec![$(
                 ErrorLabel {
                     msg: String::from($label_text),
-                    variant: ErrorLabelVariants::Help,
+                    variant: ErrorLabelVariants::Help
                 }
             ),*]
         }
     };
     ($diagnostic: ident, $range: expr, $($vars: expr),*) => {
         BaseError {
-            msg: format_diagnostic(&Diagnostics::$diagnostic, vec![$($vars),*]),
+            msg: $crate::diagnostics::messages::$diagnostic($($vars),*),
             range: $range,
+            code: Some(Diagnostics::$diagnostic.code),
+            severity: Severity::Error,
             labels: vec![]
         }
     };
     ($diagnostic: ident, $range: expr) => {
         BaseError {
-            msg: Diagnostics::$diagnostic.message.to_string(),
+            msg: $crate::diagnostics::messages::$diagnostic(),
             range: $range,
+            code: Some(Diagnostics::$diagnostic.code),
+            severity: Severity::Error,
             labels: vec![]
         }
     };
@@ -186,17 +393,9 @@ macro_rules! err {
         BaseError {
             msg: $diagnostic,
             range: $range,
+            code: None,
+            severity: Severity::Error,
             labels: vec![]
         }
     }
 }
-
-#[macro_export]
-macro_rules! dia {
-    ($diagnostic: ident, $($vars: expr),*) => {
-        format_diagnostic(&Diagnostics::$diagnostic, vec![$($vars),*])
-    };
-    ($diagnostic: ident) => {
-        format_diagnostic(&Diagnostics::$diagnostic, vec![])
-    }
-}
\ No newline at end of file
@@ -0,0 +1,67 @@
+
+use std::sync::{OnceLock, atomic::{AtomicUsize, Ordering}};
+use fluent_bundle::{FluentArgs, FluentResource, concurrent::FluentBundle};
+use unic_langid::LanguageIdentifier;
+use crate::format_code;
+
+// The bundled message catalogs. English is first, because it's the fallback for messages which are missing from the other catalogs.
+pub const CATALOGS: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en.ftl")),
+    ("es", include_str!("../locales/es.ftl"))
+];
+
+static BUNDLES: OnceLock<Vec<FluentBundle<FluentResource>>> = OnceLock::new();
+static SELECTED: AtomicUsize = AtomicUsize::new(0);
+
+fn bundles() -> &'static [FluentBundle<FluentResource>] {
+    BUNDLES.get_or_init(|| CATALOGS.iter().map(|(lang, source)| {
+        let lang_id: LanguageIdentifier = lang.parse().unwrap();
+        let mut bundle = FluentBundle::new_concurrent(vec![lang_id]);
+        // The unicode isolation marks around arguments show up as garbage in most terminals
+        bundle.set_use_isolating(false);
+        let resource = FluentResource::try_new(source.to_string()).unwrap_or_else(|_| panic!("The \"{}\" catalog has syntax errors.", lang));
+        bundle.add_resource(resource).unwrap_or_else(|_| panic!("The \"{}\" catalog has duplicate messages.", lang));
+        bundle
+    }).collect())
+}
+
+// Accepts both languages (`es`) and locales in the format of the `LANG` environment variable (`es_ES.UTF-8`).
+// Returns false if there's no catalog for the language.
+pub fn set_language(lang: &str) -> bool {
+    let lang = lang.split(['_', '-', '.']).next().unwrap_or("").to_lowercase();
+    if let Some(ind) = CATALOGS.iter().position(|(name, _)| *name == lang) {
+        SELECTED.store(ind, Ordering::Relaxed);
+        true
+    } else {
+        false
+    }
+}
+
+pub fn get_language() -> &'static str {
+    CATALOGS[SELECTED.load(Ordering::Relaxed)].0
+}
+
+fn format_message(bundle: &FluentBundle<FluentResource>, code: u16, args: &[(&str, &str)]) -> Option<Result<String, String>> {
+    let pattern = bundle.get_message(&format_code(code))?.value()?;
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, *value);
+    }
+    let mut errors = vec![];
+    let message = bundle.format_pattern(pattern, Some(&fluent_args), &mut errors).into_owned();
+    Some(if let Some(error) = errors.first() { Err(error.to_string()) } else { Ok(message) })
+}
+
+// Formats the message of the diagnostic in the selected language, or in English if the catalog doesn't have it
+pub fn get_message(code: u16, args: &[(&str, &str)]) -> String {
+    let bundles = bundles();
+    let selected = &bundles[SELECTED.load(Ordering::Relaxed)];
+    format_message(selected, code, args).or_else(|| format_message(&bundles[0], code, args))
+        .map_or_else(|| format_code(code), |res| res.unwrap_or_else(|message| message))
+}
+
+// Formats the message with the catalog of the language only, and fails if the message is missing or uses unknown arguments
+pub fn get_message_in(lang: &str, code: u16, args: &[(&str, &str)]) -> Result<String, String> {
+    let ind = CATALOGS.iter().position(|(name, _)| *name == lang).ok_or_else(|| format!("There's no catalog for \"{}\".", lang))?;
+    format_message(&bundles()[ind], code, args).unwrap_or_else(|| Err(format!("{} is missing from the \"{}\" catalog.", format_code(code), lang)))
+}
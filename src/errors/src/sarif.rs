@@ -0,0 +1,160 @@
+use serde_json::{json, Value};
+use super::*;
+use super::json::{to_json_range, severity_to_str};
+
+// Converts diagnostics to a SARIF 2.1.0 log, which is used by code scanning tools
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+// Relative paths are resolved against this base, which points to the current working directory
+const SRCROOT: &str = "SRCROOT";
+
+fn encode_uri_path(path: &str) -> String {
+    let mut res = String::new();
+    for byte in path.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => res.push(byte as char),
+            _ => res.push_str(&format!("%{:02X}", byte))
+        }
+    }
+    res
+}
+
+fn to_uri(path: &str) -> Option<String> {
+    let path = path.replace('\\', "/");
+    if path.starts_with('/') { Some(format!("file://{}", encode_uri_path(&path))) }
+    else if path.chars().nth(1) == Some(':') { Some(format!("file:///{}", encode_uri_path(&path))) }
+    else { None }
+}
+
+fn to_artifact_location(filename: &str) -> Value {
+    match to_uri(filename) {
+        Some(uri) => json!({ "uri": uri }),
+        None => json!({ "uri": encode_uri_path(&filename.replace('\\', "/")), "uriBaseId": SRCROOT })
+    }
+}
+
+fn to_sarif_region(range: &Range, content: Option<&str>) -> Value {
+    let range = to_json_range(range, content);
+    json!({
+        "startLine": range.start.line,
+        "startColumn": range.start.column,
+        "endLine": range.end.line,
+        "endColumn": range.end.column,
+        "byteOffset": range.start.byte,
+        "byteLength": range.end.byte.saturating_sub(range.start.byte)
+    })
+}
+
+fn to_sarif_location(range: &Range, filename: &str, content: Option<&str>) -> Value {
+    json!({
+        "physicalLocation": {
+            "artifactLocation": to_artifact_location(filename),
+            "region": to_sarif_region(range, content)
+        }
+    })
+}
+
+fn to_sarif_rule(diagnostic: &Diagnostic) -> Value {
+    let message = diagnostic.message();
+    let mut rule = json!({
+        "id": format_code(diagnostic.code),
+        "name": diagnostic.name,
+        "shortDescription": { "text": message }
+    });
+    if let Some(explanation) = explanations::get_explanation(diagnostic.code) {
+        rule["fullDescription"] = json!({ "text": explanation.lines().next().unwrap_or(&message) });
+        rule["help"] = json!({ "text": explanation });
+    }
+    rule
+}
+
+fn to_sarif_result(err: &BaseError, source_map: &SourceMap) -> Value {
+    let filename = source_map.get_name(err.range.file()).unwrap_or_default();
+    let content = source_map.get_content(err.range.file());
+    let mut text = err.msg.clone();
+    let mut related = vec![];
+    let mut fixes = vec![];
+    for label in &err.labels {
+        match &label.variant {
+            ErrorLabelVariants::Help => {
+                text.push_str("\nHelp: ");
+                text.push_str(&label.msg);
+            },
+            ErrorLabelVariants::Suggestion(suggestion) => {
+                let file = suggestion.range.file();
+                fixes.push(json!({
+                    "description": { "text": label.msg },
+                    "artifactChanges": [{
+                        "artifactLocation": to_artifact_location(source_map.get_name(file).unwrap_or_default()),
+                        "replacements": [{
+                            "deletedRegion": to_sarif_region(&suggestion.range, source_map.get_content(file)),
+                            "insertedContent": { "text": suggestion.replacement }
+                        }]
+                    }]
+                }));
+            },
+            ErrorLabelVariants::Sub(range) => {
+                let mut location = to_sarif_location(range, source_map.get_name(range.file()).unwrap_or_default(), source_map.get_content(range.file()));
+                location["id"] = json!(related.len());
+                location["message"] = json!({ "text": label.msg });
+                related.push(location);
+            }
+        }
+    }
+    let mut result = json!({
+        "level": severity_to_str(err.severity),
+        "message": { "text": text },
+        "locations": [to_sarif_location(&err.range, filename, content)]
+    });
+    if let Some(code) = err.code {
+        result["ruleId"] = json!(format_code(code));
+        if let Some(index) = Diagnostics::ALL.iter().position(|diagnostic| diagnostic.code == code) {
+            result["ruleIndex"] = json!(index);
+        }
+    }
+    if !related.is_empty() {
+        result["relatedLocations"] = json!(related);
+    }
+    if !fixes.is_empty() {
+        result["fixes"] = json!(fixes);
+    }
+    result
+}
+
+pub trait SarifFormatter: ErrorFormatter {
+
+    // Creates a SARIF log with a single run, which contains the diagnostics of all collectors
+    fn format_sarif(&self, collectors: &[&ErrorCollector]) -> String {
+        let mut results = vec![];
+        for collector in collectors {
+            for err in &collector.collected {
+                results.push(to_sarif_result(err, self.source_map()));
+            }
+        }
+        let mut base_ids = json!({ SRCROOT: { "description": { "text": "The directory the compiler was run from" } } });
+        if let Some(uri) = std::env::current_dir().ok().and_then(|dir| to_uri(&dir.to_string_lossy())) {
+            base_ids[SRCROOT]["uri"] = json!(format!("{}/", uri.trim_end_matches('/')));
+        }
+        let log = json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "lazy",
+                        "informationUri": "https://github.com/lazy-lang/Lazy",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": Diagnostics::ALL.iter().map(to_sarif_rule).collect::<Vec<Value>>()
+                    }
+                },
+                "originalUriBaseIds": base_ids,
+                "columnKind": "unicodeCodePoints",
+                "results": results
+            }]
+        });
+        serde_json::to_string_pretty(&log).unwrap()
+    }
+}
+
+impl<T: ErrorFormatter> SarifFormatter for T {}
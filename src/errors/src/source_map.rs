@@ -0,0 +1,44 @@
+use super::FileId;
+
+pub struct SourceFile {
+    pub name: String,
+    pub content: String
+}
+
+// Stores the name and text of every file, so spans only need to carry a `FileId`
+#[derive(Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>
+}
+
+impl SourceMap {
+
+    pub fn new() -> Self {
+        Self { files: vec![] }
+    }
+
+    // Ids start from 1, because 0 is reserved for spans which don't belong to any file
+    pub fn add(&mut self, name: &str, content: String) -> FileId {
+        self.files.push(SourceFile { name: name.to_string(), content });
+        FileId(self.files.len() as u32)
+    }
+
+    pub fn get(&self, file: FileId) -> Option<&SourceFile> {
+        self.files.get((file.0 as usize).checked_sub(1)?)
+    }
+
+    pub fn get_name(&self, file: FileId) -> Option<&str> {
+        Some(&self.get(file)?.name)
+    }
+
+    pub fn get_content(&self, file: FileId) -> Option<&str> {
+        Some(&self.get(file)?.content)
+    }
+
+    // The latest file with the name
+    pub fn find(&self, name: &str) -> Option<FileId> {
+        let ind = self.files.iter().rposition(|file| file.name == name)?;
+        Some(FileId(ind as u32 + 1))
+    }
+
+}
@@ -1,15 +1,25 @@
 
-use super::tokenizer::{Tokenizer, TokenType};
+use super::tokenizer::{Tokenizer, Token, TokenType};
 pub use errors::{LoC};
 pub mod model;
 pub mod utils;
 use model::*;
 use errors::*;
 
+// The default maximum nesting depth of expressions, typings and deconstruct patterns.
+// Only real nesting counts - wrapped expressions, blocks, function bodies... Chains of binary operators,
+// unary operators and suffixes (a.b.c, a()()) are parsed in loops, so they don't count towards the depth.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
 pub struct Parser<'a> {
     pub tokens: Tokenizer<'a>,
+    pub max_depth: usize,
+    // In script mode, expressions are allowed at the top level. They're collected into an implicit main entry.
+    pub script_mode: bool,
+    depth: usize,
     is_last_block: bool,
     allow_exp_statements: bool,
+    in_template: bool,
     parsed_main: bool
 }
 
@@ -18,9 +28,13 @@ impl<'a> Parser<'a> {
     pub fn new(source: &str, err_collector: &'a mut ErrorCollector) -> Self {
         Parser {
             tokens: Tokenizer::new(source, err_collector),
+            max_depth: DEFAULT_MAX_DEPTH,
+            script_mode: false,
+            depth: 0,
             parsed_main: false,
             is_last_block: false,
-            allow_exp_statements: false
+            allow_exp_statements: false,
+            in_template: false
         }
     }
 
@@ -38,45 +52,78 @@ impl<'a> Parser<'a> {
         }
     }
 
+    fn enter_nested(&mut self) -> LazyResult<()> {
+        self.depth += 1;
+        if self.depth > self.max_depth {
+            return Err(err!(TOO_DEEPLY_NESTED, self.tokens.range_here(), &self.max_depth.to_string()));
+        }
+        Ok(())
+    }
+
+    // Runs the parse function one level deeper, so deeply nested code results in an error instead of a stack overflow
+    fn nested<T, F: FnOnce(&mut Self) -> LazyResult<T>>(&mut self, parse_fn: F) -> LazyResult<T> {
+        let depth = self.depth;
+        let res = self.enter_nested().and_then(|_| parse_fn(self));
+        self.depth = depth;
+        res
+    }
+
+    // Inside template interpolations, a single colon starts the format specifier
+    fn is_format_spec_next(&mut self) -> bool {
+        self.in_template && self.tokens.is_next(TokenType::Punc(':')) && self.tokens.input.peek(0) != Some(':')
+    }
+
+    // Operators of the same precedence are parsed in a loop, so only operators with a higher precedence recurse
     fn parse_binary(&mut self, left_tok: ASTExpression, prec: i8) -> LazyResult<ASTExpression> {
-        let start = self.tokens.input.loc();
-        let next = self.tokens.peek();
-        if next.is_none() { 
-            return Ok(left_tok)
-         };
-        if let TokenType::Op(op_start) = next.unwrap().val {
+        let mut left_tok = left_tok;
+        loop {
+            let start = self.tokens.input.loc();
+            let op_start = match self.tokens.peek() {
+                Some(Token { val: TokenType::Op(op_start), .. }) => *op_start,
+                _ => return Ok(left_tok)
+            };
             let opval = self.tokens.parse_full_op(Some(op_start));
             let other_prec = Self::get_prec(&opval);
-            if other_prec == -1 {
+            if other_prec == -1 || other_prec <= prec {
                 return Ok(left_tok)
             }
-            if other_prec > prec {
-                self.tokens.consume();
-                let exp = if let Some(exp) = self.parse_expression_part(false)? {
-                    exp
-                } else {
-                    return Ok(left_tok);
-                };
-                let right = self.parse_binary(exp, other_prec)?;
-                return self.parse_binary(ASTExpression::Binary(ASTBinary {
-                    op: opval.to_string(),
-                    left: Box::from(left_tok),
-                    right: Box::from(right),
-                    range: start.end(&self.tokens.last_loc)
-                }), prec);
-            }
-            Ok(left_tok)
-        } else {
-            Ok(left_tok)
+            self.tokens.consume();
+            let exp = if let Some(exp) = self.parse_expression_part(false)? {
+                exp
+            } else {
+                return Ok(left_tok);
+            };
+            let right = self.parse_binary(exp, other_prec)?;
+            left_tok = ASTExpression::Binary(ASTBinary {
+                op: opval.to_string(),
+                left: Box::from(left_tok),
+                right: Box::from(right),
+                range: start.end(&self.tokens.last_loc)
+            });
         }
     }
 
-    fn parse_suffix(&mut self, token: ASTExpression, parse_generics: bool) -> LazyResult<ASTExpression> {
+    // Suffixes are parsed in a loop, so long chains like `a.b.c()` don't count towards the nesting depth
+    fn parse_suffix(&mut self, token: ASTExpression) -> LazyResult<ASTExpression> {
+        let mut token = token;
+        loop {
+            token = match self.parse_one_suffix(token)? {
+                (token, true) => token,
+                (token, false) => return Ok(token)
+            };
+        }
+    }
+
+    // Returns the expression with the suffix, and whether another suffix can follow it
+    fn parse_one_suffix(&mut self, token: ASTExpression) -> LazyResult<(ASTExpression, bool)> {
         let start = self.tokens.input.loc();
+        if self.is_format_spec_next() {
+            return Ok((token, false));
+        }
         let next_token = if let Some(t) = self.tokens.peek() { t } else {
-            return Ok(token);
+            return Ok((token, false));
         };
-        match &next_token.val {
+        Ok(match &next_token.val {
             TokenType::Op(val) => {
                 match val {
                     '.' => {
@@ -91,34 +138,34 @@ impl<'a> Parser<'a> {
                             let end = if let Some(end) = self.parse_expression_part(true)? { end } else {
                                 return Err(err!(END_OF_ITER, start.end(&self.tokens.last_loc)));
                             };
-                            return Ok(ASTExpression::Iterator(
+                            return Ok((ASTExpression::Iterator(
                                 ASTIterator {
                                     start: Box::from(token),
                                     end: Box::from(end),
                                     inclusive,
                                     range: start.end(&self.tokens.last_loc)
                                 }
-                            ));
+                            ), false));
                         }
                         let target = self.parse_varname(true, false, !matches!(token, ASTExpression::Int(_) | ASTExpression::Float(_)), true)?.0;
-                        self.parse_suffix(ASTExpression::DotAccess(
+                        (ASTExpression::DotAccess(
                             ASTDotAccess {
                                 target: target,
                                 value: Box::from(token),
                                 range: start.end(&self.tokens.last_loc)
                             }
-                        ), parse_generics)
+                        ), true)
                     },
                     '?' => {
                         self.tokens.consume();
-                        self.parse_suffix(ASTExpression::Optional(
+                        (ASTExpression::Optional(
                             ASTOptional {
                                 value: Box::from(token),
                                 range: start.end(&self.tokens.last_loc)
                             }
-                        ), parse_generics)
+                        ), true)
                     },
-                    _ => Ok(token)
+                    _ => (token, false)
                 }
             },
             TokenType::Punc(punc) => {
@@ -126,14 +173,14 @@ impl<'a> Parser<'a> {
                     '(' => {
                         self.tokens.consume();
                         let args = self.parse_expression_list(')')?;
-                        self.parse_suffix(ASTExpression::Call(
+                        (ASTExpression::Call(
                             ASTCall {
                                 target: Box::from(token),
                                 typings: None,
                                 args,
                                 range: start.end(&self.tokens.last_loc)
                             }
-                        ), parse_generics)
+                        ), true)
                     },
                     '[' => {
                         self.tokens.consume();
@@ -141,29 +188,29 @@ impl<'a> Parser<'a> {
                             return Err(err!(EXPECTED, start.end(&self.tokens.last_loc), "expression"));
                         };
                         self.tokens.skip_or_err(TokenType::Punc(']'), None)?;
-                        self.parse_suffix(ASTExpression::IndexAccess(
+                        (ASTExpression::IndexAccess(
                             ASTIndexAccess {
                             target: Box::from(target),
                             value: Box::from(token),
                             range: start.end(&self.tokens.last_loc)
                             }
-                        ), parse_generics)
+                        ), true)
                     },
                     ':' => {
                         if let ASTExpression::Var(v) = token {
                             match self.parse_mod_access_or_var(v, true, true)? {
-                                ASTModAccessValues::ModAccess(mod_access) => Ok(ASTExpression::ModAccess(mod_access)),
-                                ASTModAccessValues::Var(v) => Ok(ASTExpression::Var(v.value))
+                                ASTModAccessValues::ModAccess(mod_access) => (ASTExpression::ModAccess(mod_access), false),
+                                ASTModAccessValues::Var(v) => (ASTExpression::Var(v.value), false)
                             }
                         } else {
-                            Err(err!(EXPECTED, self.tokens.range_here(), "identifier"))
+                            return Err(err!(EXPECTED, self.tokens.range_here(), "identifier"));
                         }
                     }
-                    _ => Ok(token)
+                    _ => (token, false)
                 }
             }
-            _ => Ok(token)
-        }
+            _ => (token, false)
+        })
     }
 
     pub fn parse_mod_access_or_var_without_var(&mut self, allow_exp_end: bool, allow_typings: bool) -> LazyResult<ASTModAccessValues> {
@@ -172,7 +219,7 @@ impl<'a> Parser<'a> {
     }
 
     pub fn parse_mod_access_or_var(&mut self, start: ASTVar, allow_exp_end: bool, allow_typings: bool) -> LazyResult<ASTModAccessValues> {
-        if !self.tokens.is_next(TokenType::Punc(':')) {
+        if !self.tokens.is_next(TokenType::Punc(':')) || self.is_format_spec_next() {
             let r = start.range;
             let typings = if self.tokens.is_next(TokenType::Op('<')) {
                 if !allow_typings {
@@ -184,11 +231,12 @@ impl<'a> Parser<'a> {
             return Ok(ASTModAccessValues::Var(ASTVarTyping { value: start, range: r, typings }));
         };
 
+        let start_loc = start.range.start;
         let mut path: Vec<ASTVar> = vec![start];
-        let start = self.tokens.input.loc();
-        while self.tokens.is_next(TokenType::Punc(':')) {
+        while self.tokens.is_next(TokenType::Punc(':')) && !self.is_format_spec_next() {
             self.tokens.consume();
-            self.tokens.skip_or_err(TokenType::Punc(':'), Some(err!(EXPECTED, self.tokens.range_here(), "Another colon (:)"; ["Add another colon to make the mod access expression (Module::Item)"])))?;
+            let after_colon = self.tokens.input.loc().to_range();
+            self.tokens.skip_or_err(TokenType::Punc(':'), Some(err!(EXPECTED, self.tokens.range_here(), "Another colon (:)").with_suggestion("Add another colon to make the mod access expression (Module::Item)", after_colon, ":", Applicability::MachineApplicable)))?;
             if let Some(tok) = self.tokens.consume() {
                 match tok.val {
                     TokenType::Var(v) => path.push(ASTVar { value: v, range: tok.range }),
@@ -219,7 +267,7 @@ impl<'a> Parser<'a> {
         Ok(ASTModAccessValues::ModAccess(
             ASTModAccess {
                 path,
-                range: start.end(&self.tokens.last_loc),
+                range: start_loc.end(&self.tokens.last_loc),
                 typings,
                 init
             }
@@ -227,6 +275,10 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_typing(&mut self, allow_fn_keyword: bool, allow_optional_after_var: bool, allow_mod: bool) -> LazyResult<ASTTypings> {
+        self.nested(|parser| parser.parse_typing_inner(allow_fn_keyword, allow_optional_after_var, allow_mod))
+    }
+
+    fn parse_typing_inner(&mut self, allow_fn_keyword: bool, allow_optional_after_var: bool, allow_mod: bool) -> LazyResult<ASTTypings> {
         let range = self.tokens.input.loc();
         let maybe_token = self.tokens.peek();
         let t = match maybe_token {
@@ -346,7 +398,7 @@ impl<'a> Parser<'a> {
             let range = utils::full_expression_range(&exp);
             res.push(exp);
             if !self.is_last_block { 
-                self.tokens.skip_or_err(TokenType::Punc(';'), Some(err!(SEMICOLON, range)))?; 
+                self.tokens.skip_or_err(TokenType::Punc(';'), Some(err!(SEMICOLON, range).with_suggestion("Add a semicolon", range.end.to_range(), ";", Applicability::MaybeIncorrect)))?; 
             };
         }
         self.tokens.skip_or_err(TokenType::Punc('}'), Some(err!(END_OF_BLOCK, range.end(&self.tokens.last_loc))))?;
@@ -386,7 +438,7 @@ impl<'a> Parser<'a> {
 
     fn parse_pair_list(&mut self, allow_without_val: bool, closing_punc: char) -> LazyResult<ASTPairList> {
         let range = self.tokens.input.loc();
-        let mut res: Vec<(String, Option<ASTExpression>)> = vec![];
+        let mut res: Vec<(String, Option<ASTExpression>, Range)> = vec![];
         let mut has_consumed_bracket = false;
         while !self.tokens.is_next(TokenType::Punc(closing_punc)) {
             let tok_start = self.tokens.input.loc();
@@ -396,20 +448,20 @@ impl<'a> Parser<'a> {
                     if !allow_without_val {
                         return Err(err!(EXPECTED, tok_start.end(&self.tokens.last_loc), "value"));
                     }
-                    res.push((key.value, None));
+                    res.push((key.value, None, key.range));
                 },
                 ':' => {
                     let exp = if let Some(exp) = self.parse_expression()? { Some(exp) } else {
                         return Err(err!(EXPECTED, tok_start.end(&self.tokens.last_loc), "expression"));
                     };
-                    res.push((key.value, exp));
+                    res.push((key.value, exp, key.range));
                 },
                 ch if ch == closing_punc => {
                     if !allow_without_val {
                         return Err(err!(EXPECTED, tok_start.end(&self.tokens.last_loc), "typing"));
                     }
                     has_consumed_bracket = true;
-                    res.push((key.value, None));
+                    res.push((key.value, None, key.range));
                     break;
                 },
                 _ => {}
@@ -444,20 +496,72 @@ impl<'a> Parser<'a> {
         })
     }
 
-    fn parse_varname_list(&mut self, closing_punc: char) -> LazyResult<ASTVarList> {
+    fn parse_deconstruct_pattern(&mut self) -> LazyResult<ASTDeclareTypes> {
+        self.nested(|parser| parser.parse_deconstruct_pattern_inner())
+    }
+
+    fn parse_deconstruct_pattern_inner(&mut self) -> LazyResult<ASTDeclareTypes> {
+        let token = if let Some(t) = self.tokens.consume() { t } else {
+            return Err(err!(EXPECTED, self.tokens.range_here(), "identifier or deconstruct pattern"));
+        };
+        match token.val {
+            TokenType::Punc('[') => Ok(ASTDeclareTypes::TupleDeconstruct(self.parse_deconstruct_list(']')?)),
+            TokenType::Punc('{') => Ok(ASTDeclareTypes::StructDeconstruct(self.parse_deconstruct_list('}')?)),
+            TokenType::Var(v) => Ok(ASTDeclareTypes::Var(ASTVar { value: v, range: token.range })),
+            _ => Err(err!(EXPECTED_FOUND, token.range, "identifier or deconstruct pattern", &token.val.to_string()))
+        }
+    }
+
+    fn parse_deconstruct_list(&mut self, closing_punc: char) -> LazyResult<ASTDeconstructList> {
         let range = self.tokens.input.loc();
-        let mut values: Vec<ASTVar> = vec![];
+        let is_struct = closing_punc == '}';
+        let mut values: Vec<ASTDeconstructItem> = vec![];
         let mut is_first = true;
         while !self.tokens.is_next(TokenType::Punc(closing_punc)) {
             if !is_first {
                 self.tokens.skip_or_err(TokenType::Punc(','), None)?;
             };
-            let exp = self.parse_varname(false, false, false, true)?.0;
-            values.push(exp);
+            if let Some(last) = values.last() {
+                if last.spread {
+                    return Err(err!(REST_NOT_LAST, last.range));
+                }
+            };
+            let item_start = self.tokens.input.loc();
+            let spread = self.tokens.is_next_full_op(&['.', '.', '.']);
+            let (field, pattern) = if spread {
+                (None, ASTDeclareTypes::Var(self.parse_varname(false, false, false, false)?.0))
+            } else if is_struct {
+                let field = self.parse_varname(false, false, false, true)?.0;
+                if self.tokens.is_next(TokenType::Punc(':')) {
+                    self.tokens.consume();
+                    (Some(field), self.parse_deconstruct_pattern()?)
+                } else {
+                    let binding = ASTVar { value: field.value.clone(), range: field.range };
+                    (Some(field), ASTDeclareTypes::Var(binding))
+                }
+            } else {
+                (None, self.parse_deconstruct_pattern()?)
+            };
+            let default_value = if self.tokens.is_next(TokenType::Op('=')) {
+                if spread {
+                    return Err(err!(DISALLOWED, self.tokens.range_here(), "default value for a rest element"));
+                }
+                self.tokens.consume();
+                if let Some(exp) = self.parse_expression()? { Some(exp) } else {
+                    return Err(err!(EXPECTED, self.tokens.range_here(), "expression"));
+                }
+            } else { None };
+            values.push(ASTDeconstructItem {
+                field,
+                pattern,
+                default_value,
+                spread,
+                range: item_start.end(&self.tokens.last_loc)
+            });
             is_first = false;
         };
         self.tokens.skip_or_err(TokenType::Punc(closing_punc), None)?;
-        Ok(ASTVarList {
+        Ok(ASTDeconstructList {
             values,
             range: range.end(&self.tokens.last_loc)
         })
@@ -519,7 +623,9 @@ impl<'a> Parser<'a> {
                 let default_value = if let Some(exp) = self.parse_expression()? { Some(exp) } else {
                     return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
                 };
-                res.push(ASTPairTypingItem {name: key.value, value: None, default_value, modifiers, spread: is_spread});
+                res.push(ASTPairTypingItem {name: key.value, name_range: key.range, value: None, default_value, modifiers, spread: is_spread});
+                modifiers.clear();
+                if !self.tokens.is_next(TokenType::Punc(closing_punc)) { self.tokens.expect_punc(&[','], None)?; };
                 continue;
             }
             match self.tokens.expect_punc(&[',', ':', closing_punc], None)? {
@@ -527,7 +633,7 @@ impl<'a> Parser<'a> {
                     if !allow_without_val {
                         return Err(err!(EXPECTED, tok_range.end(&self.tokens.last_loc),  "type"));
                     }
-                    res.push(ASTPairTypingItem {name: key.value, value: None, default_value: None, modifiers, spread: is_spread});
+                    res.push(ASTPairTypingItem {name: key.value, name_range: key.range, value: None, default_value: None, modifiers, spread: is_spread});
                     modifiers.clear();
                 },
                 ':' => {
@@ -541,15 +647,16 @@ impl<'a> Parser<'a> {
                             return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
                         })
                     } else { None };
-                    res.push(ASTPairTypingItem { name: key.value, value: Some(exp), default_value, modifiers, spread: is_spread});
+                    res.push(ASTPairTypingItem { name: key.value, name_range: key.range, value: Some(exp), default_value, modifiers, spread: is_spread});
                     modifiers.clear();
+                    if !self.tokens.is_next(TokenType::Punc(closing_punc)) { self.tokens.expect_punc(&[','], None)?; };
                 },
                 ch if ch == closing_punc => {
                     if !allow_without_val {
                         return Err(err!(EXPECTED, tok_range.end(&self.tokens.last_loc),  "type"));
                     }
                     has_consumed_bracket = true;
-                    res.push(ASTPairTypingItem { name: key.value, value: None, default_value: None, modifiers, spread: is_spread});
+                    res.push(ASTPairTypingItem { name: key.value, name_range: key.range, value: None, default_value: None, modifiers, spread: is_spread});
                     modifiers.clear();
                     break;
                 },
@@ -699,7 +806,372 @@ impl<'a> Parser<'a> {
         }
     }
 
+    fn parse_template_literal(&mut self, start: Range) -> LazyResult<ASTTempStr> {
+        let mut parts: Vec<ASTTempStrPart> = vec![];
+        let mut string = String::new();
+        let mut string_start = self.tokens.input.loc();
+        let mut has_interpolations = false;
+        let mut is_prev_escape = false;
+        loop {
+            let ch = if let Some(ch) = self.tokens.input.consume() { ch } else {
+                return Err(err!(END_OF_STR, start.end_with(&self.tokens.input.loc())));
+            };
+            match ch {
+                '`' if !is_prev_escape => break,
+                '$' if !is_prev_escape && self.tokens.input.peek(0) == Some('{') => {
+                    let interpolation_start = self.tokens.input.loc();
+                    if !string.is_empty() {
+                        parts.push(ASTTempStrPart::Literal(ASTStr { value: std::mem::take(&mut string), range: string_start.end(&interpolation_start) }));
+                    }
+                    self.tokens.input.consume();
+                    let was_in_template = self.in_template;
+                    self.in_template = true;
+                    let value = self.parse_expression();
+                    self.in_template = was_in_template;
+                    let value = if let Some(exp) = value? { exp } else {
+                        return Err(err!(EXPECTED, self.tokens.range_here(), "expression"));
+                    };
+                    let format = if self.tokens.is_next(TokenType::Punc(':')) {
+                        self.tokens.consume();
+                        Some(self.parse_format_spec()?)
+                    } else {
+                        self.tokens.skip_or_err(TokenType::Punc('}'), Some(err!(EXPECTED, self.tokens.range_here(), "end of template interpolation (})")))?;
+                        None
+                    };
+                    parts.push(ASTTempStrPart::Interpolation(ASTTempStrInterpolation { value, format, range: interpolation_start.end(&self.tokens.input.loc()) }));
+                    has_interpolations = true;
+                    string_start = self.tokens.input.loc();
+                },
+                '\\' if !is_prev_escape => is_prev_escape = true,
+                _ => {
+                    is_prev_escape = false;
+                    string.push(ch);
+                }
+            }
+        };
+        if !has_interpolations {
+            let range = start.end_with(&self.tokens.input.loc());
+            let mut err = err!(POINTLESS_TEMPLATE, range).with_severity(Severity::Warning);
+            if !string.contains('"') {
+                err = err.with_suggestion("Use a string literal", range, &format!("\"{}\"", string), Applicability::MachineApplicable);
+            }
+            self.tokens.errors.push(err);
+        }
+        if !string.is_empty() {
+            let end = self.tokens.input.loc();
+            parts.push(ASTTempStrPart::Literal(ASTStr { value: string, range: string_start.end(&end) }));
+        }
+        Ok(ASTTempStr {
+            parts,
+            range: start.end_with(&self.tokens.input.loc())
+        })
+    }
+
+    // Parses a format specifier in the form of [[fill]align][+][#][0][width][.precision][kind], up until the closing bracket
+    fn parse_format_spec(&mut self) -> LazyResult<ASTFormatSpec> {
+        let start = self.tokens.input.loc();
+        let mut raw: Vec<char> = vec![];
+        loop {
+            match self.tokens.input.consume() {
+                Some('}') => break,
+                Some(ch) => raw.push(ch),
+                None => return Err(err!(END_OF_STR, start.end(&self.tokens.input.loc())))
+            }
+        };
+        let range = start.end(&self.tokens.input.loc());
+        let to_align = |ch: char| match ch {
+            '<' => Some(ASTFormatAlign::Left),
+            '>' => Some(ASTFormatAlign::Right),
+            '^' => Some(ASTFormatAlign::Center),
+            _ => None
+        };
+        let mut spec = ASTFormatSpec { fill: None, align: None, sign: false, alternate: false, zero_pad: false, width: None, precision: None, kind: None, range };
+        let mut ind = 0;
+        if raw.len() >= 2 && to_align(raw[1]).is_some() {
+            spec.fill = Some(raw[0]);
+            spec.align = to_align(raw[1]);
+            ind = 2;
+        } else if let Some(align) = raw.first().and_then(|ch| to_align(*ch)) {
+            spec.align = Some(align);
+            ind = 1;
+        };
+        if raw.get(ind) == Some(&'+') { spec.sign = true; ind += 1; };
+        if raw.get(ind) == Some(&'#') { spec.alternate = true; ind += 1; };
+        if raw.get(ind) == Some(&'0') { spec.zero_pad = true; ind += 1; };
+        let mut width = String::new();
+        while let Some(ch) = raw.get(ind).filter(|ch| ch.is_ascii_digit()) {
+            width.push(*ch);
+            ind += 1;
+        };
+        if !width.is_empty() { spec.width = width.parse().ok() };
+        if raw.get(ind) == Some(&'.') {
+            ind += 1;
+            let mut precision = String::new();
+            while let Some(ch) = raw.get(ind).filter(|ch| ch.is_ascii_digit()) {
+                precision.push(*ch);
+                ind += 1;
+            };
+            if precision.is_empty() {
+                return Err(err!(INVALID_FORMAT_SPEC, range, &raw.iter().collect::<String>(); ["A precision must be a number, for example `.2`"]));
+            }
+            spec.precision = precision.parse().ok();
+        };
+        if let Some(kind) = raw.get(ind) {
+            if !matches!(kind, 'x' | 'X' | 'b' | 'o' | 'e' | 'E' | '?') {
+                return Err(err!(INVALID_FORMAT_SPEC, range, &raw.iter().collect::<String>(); ["The format kind must be one of x, X, b, o, e, E or ?"]));
+            }
+            spec.kind = Some(*kind);
+            ind += 1;
+        };
+        if ind != raw.len() {
+            return Err(err!(INVALID_FORMAT_SPEC, range, &raw.iter().collect::<String>()));
+        }
+        Ok(spec)
+    }
+
+    fn parse_prefix_expression(&mut self, range: Range, value: char, parse_generics_in_suffix: bool) -> LazyResult<ASTExpression> {
+        Ok(match self.tokens.parse_full_op(Some(value)).as_str() {
+            "-" | "!" | "~" => {
+                // Chains of unary operators (- - a) are collected in a loop, so they don't count towards the nesting depth
+                let mut ops = vec![(value, range)];
+                while let Some(Token { val: TokenType::Op(op @ ('-' | '!' | '~')), range }) = self.tokens.peek() {
+                    let (op, range) = (*op, *range);
+                    self.tokens.consume();
+                    let full_op = self.tokens.parse_full_op(Some(op));
+                    if full_op.len() != 1 {
+                        return Err(err!(UNEXPECTED_OP, range.end_with(&self.tokens.last_loc),  &full_op));
+                    }
+                    ops.push((op, range));
+                }
+                let mut val = if let Some(val) = self.parse_expression_part(parse_generics_in_suffix)? { val } else {
+                    return Err(err!(EXPECTED, range,  "expression"));
+                };
+                for (op, range) in ops.into_iter().rev() {
+                    val = ASTExpression::Unary(
+                        ASTUnary {
+                            op: op.to_string(),
+                            value: Box::from(val),
+                            range
+                        }
+                    );
+                }
+                val
+            },
+            val @ ".." | val @ "..=" => ASTExpression::Iterator(ASTIterator {
+                    start: Box::from(ASTExpression::Int(ASTInt { value: 0, range: range.clone() })),
+                    end: if let Some(exp) = self.parse_expression()? { Box::from(exp) } else {
+                        return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
+                    },
+                    inclusive: val == "..=",
+                    range: range.end_with(&self.tokens.last_loc)
+            }),
+            "..." => {
+                ASTExpression::Spread(
+                    ASTSpread {
+                        value: if let Some(exp) = self.parse_expression()? { Box::from(exp) } else {
+                            return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
+                        },
+                        range: range.end_with(&self.tokens.last_loc)
+                    }
+                )
+            }
+            val @ _ => {
+                return Err(err!(UNEXPECTED_OP, range.end_with(&self.tokens.last_loc),  val));
+            }
+        })
+    }
+
+    // Returns the parsed expression, and whether suffixes (calls, dot access...) can follow it
+    fn parse_keyword_expression(&mut self, range: Range, val: &str) -> LazyResult<(ASTExpression, bool)> {
+        let exp = match val {
+            "let" | "const" => return Ok((self.parse_declare(range, val == "const")?, false)),
+            "fn" => ASTExpression::Function(self.parse_function(true)?),
+            "if" => return Ok((self.parse_if(range)?, false)),
+            "for" => return Ok((self.parse_for_in(range)?, false)),
+            "while" => return Ok((self.parse_while(range)?, false)),
+            "match" => return Ok((self.parse_match(range)?, false)),
+            "new" => {
+                let target = self.parse_mod_access_or_var_without_var(false, true)?;
+                let typings = if self.tokens.is_next(TokenType::Op('<')) {
+                    self.tokens.consume();
+                    Some(self.parse_typing_list(false, false, TokenType::Op('>'))?)
+                } else { None };
+                self.tokens.skip_or_err(TokenType::Punc('{'), Some(err!(EXPECTED, self.tokens.range_here(),  "struct initializor")))?;
+                ASTExpression::Init(
+                    ASTInitializor {
+                        target,
+                        params: self.parse_pair_list(true, '}')?,
+                        typings,
+                        range: range.end_with(&self.tokens.last_loc)
+                    }
+                )
+            },
+            "await" => {
+                let optional = if self.tokens.is_next(TokenType::Op('?')) {
+                    self.tokens.consume();
+                    true 
+                } else { false };
+                let expression = if let Some(exp) = self.parse_expression()? { Box::from(exp) } else {
+                    return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
+                };
+                ASTExpression::Await(
+                    ASTAwait {
+                        optional,
+                        expression,
+                        range: range.end_with(&self.tokens.last_loc)
+                    }
+                )
+            }
+            _ => {
+                return Err(err!(UNEXPECTED, range, &format!("keyword \"{}\"", val)));
+            }
+        };
+        Ok((exp, true))
+    }
+
+    fn parse_declare(&mut self, range: Range, is_const: bool) -> LazyResult<ASTExpression> {
+        let var = self.parse_deconstruct_pattern()?;
+        let typings = if self.tokens.is_next(TokenType::Punc(':')) {
+            self.tokens.consume();
+            Some(self.parse_typing(false, true, true)?)
+        } else { None };
+        let value = if self.tokens.is_next(TokenType::Op('=')) {
+            self.tokens.consume(); // Skip =
+            if let Some(exp) = self.parse_expression()? { Some(Box::from(exp)) } else {
+                return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
+            }
+        } else { 
+            if is_const {
+                return Err(err!(CONST_WITHOUT_INIT, range.end_with(&self.tokens.last_loc)));
+            }
+            None
+         };
+        Ok(ASTExpression::Declare(
+            ASTDeclare {
+                var,
+                is_const,
+                typings,
+                value,
+                range: range.end_with(&self.tokens.last_loc)
+            }
+        ))
+    }
+
+    fn parse_if(&mut self, range: Range) -> LazyResult<ASTExpression> {
+        let condition = if let Some(exp) = self.parse_expression()? { Box::from(exp) } else {
+            return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
+        };
+        let then = if let Some(exp) = self.parse_expression_or_expression_statement()? { Box::from(exp) } else {
+            return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
+        };
+        let otherwise = if self.tokens.is_next(TokenType::Kw(String::from("else"))) {
+             self.tokens.consume();
+             if let Some(exp) = self.parse_expression_or_expression_statement()? { Some(Box::from(exp)) } else {
+                return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
+            }
+        } else { None };
+        Ok(ASTExpression::If(
+            ASTIf {
+                condition,
+                then,
+                otherwise,
+                range: range.end_with(&mut self.tokens.last_loc)
+            }
+        ))
+    }
+
+    fn parse_for_in(&mut self, range: Range) -> LazyResult<ASTExpression> {
+        let var = self.parse_varname(false, false, false, false)?.0;
+        self.tokens.skip_or_err(TokenType::Kw(String::from("in")), None)?;
+        let iterator = if let Some(exp) = self.parse_expression()? { Box::from(exp) } else {
+            return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
+        };
+        let turn_off_exp_statements = !self.allow_exp_statements;
+        self.allow_exp_statements = true;
+        let body = if let Some(exp) = self.parse_expression_or_expression_statement()? { Box::from(exp) } else {
+            return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
+        };
+        if turn_off_exp_statements { self.allow_exp_statements = false; }
+        Ok(ASTExpression::ForIn(
+            ASTForIn {
+                var,
+                iterable: iterator,
+                body,
+                range: range.end_with(&self.tokens.last_loc)
+            }
+        ))
+    }
+
+    fn parse_while(&mut self, range: Range) -> LazyResult<ASTExpression> {
+        let cond = if let Some(exp) = self.parse_expression()? { Box::from(exp) } else {
+            return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
+        };
+        let turn_off_exp_statements = !self.allow_exp_statements;
+        self.allow_exp_statements = true;
+        let body = if let Some(exp) = self.parse_expression_or_expression_statement()? { Box::from(exp) } else {
+            return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
+        };
+        if turn_off_exp_statements { self.allow_exp_statements = false; }
+        Ok(ASTExpression::While(
+            ASTWhile {
+                condition: cond,
+                body,
+                range: range.end_with(&self.tokens.last_loc)
+            }
+        ))
+    }
+
+    fn parse_match(&mut self, range: Range) -> LazyResult<ASTExpression> {
+        let to_get_matched = if let Some(exp) = self.parse_expression()? { Box::from(exp) } else {
+            return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
+        };
+        self.tokens.skip_or_err(TokenType::Punc('{'), None)?;
+        let mut arms: Vec<ASTMatchArm> = vec![];
+        while !self.tokens.is_next(TokenType::Punc('}')) {
+            let match_arm_start = self.tokens.input.loc();
+            let mut possibilities: Vec<ASTMatchArmExpressions> = vec![];
+            possibilities.push(self.parse_match_arm_exp()?);
+            if self.tokens.is_next(TokenType::Op('|')) {
+                self.tokens.consume();
+                while !self.tokens.is_next(TokenType::Op('=')) && !self.tokens.is_next(TokenType::Kw(String::from("if")))  {
+                    possibilities.push(self.parse_match_arm_exp()?);
+                    if self.tokens.is_next(TokenType::Op('|')) { self.tokens.consume(); };
+                }
+            }
+            let guard = if self.tokens.is_next(TokenType::Kw(String::from("if"))) {
+                self.tokens.consume();
+                if let Some(exp) = self.parse_expression()? { Some(exp) } else {
+                    return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
+                }
+            } else { None };
+
+            self.tokens.skip_or_err_full_op("=>", None)?;
+
+            let body = if let Some(exp) = self.parse_expression()? { exp } else {
+                return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
+            };
+            if self.tokens.is_next(TokenType::Punc(',')) { self.tokens.consume(); };
+            arms.push(ASTMatchArm {
+                guard,
+                possibilities,
+                body,
+                range: match_arm_start.end(&self.tokens.last_loc)
+            });
+        }
+        self.tokens.skip_or_err(TokenType::Punc('}'), None)?;
+        self.is_last_block = true;
+        Ok(ASTExpression::Match(ASTMatch {
+            arms,
+            range: range.end_with(&self.tokens.last_loc),
+            expression: to_get_matched
+        }))
+    }
+
     fn parse_expression_part(&mut self, parse_generics_in_suffix: bool) -> LazyResult<Option<ASTExpression>> {
+        self.nested(|parser| parser.parse_expression_part_inner(parse_generics_in_suffix))
+    }
+
+    fn parse_expression_part_inner(&mut self, parse_generics_in_suffix: bool) -> LazyResult<Option<ASTExpression>> {
         self.is_last_block = false;
         let exp = {
         let token = if let Some(t) = self.tokens.consume() {
@@ -715,83 +1187,8 @@ impl<'a> Parser<'a> {
             TokenType::None => ASTExpression::None(token.range),
             TokenType::Var(value) => ASTExpression::Var(ASTVar { value, range: token.range }),
             TokenType::Bool(value) => ASTExpression::Bool(ASTBool { value, range: token.range }),
-            TokenType::TempStrStart => {
-                let mut string = String::new();
-                let mut exps: HashMap<usize, ASTExpression> = HashMap::new();
-                let mut is_prev_escape = false;
-                loop {
-                    match self.tokens.input.consume() {
-                        Some(ch) => {
-                            match ch {
-                                '`' => break,
-                                '$' if !is_prev_escape => {
-                                    self.tokens.skip_or_err(TokenType::Punc('{'), None)?;
-                                    let exp = if let Some(exp) = self.parse_expression()? { exp } else {
-                                        return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
-                                    };
-                                    self.tokens.skip_or_err(TokenType::Punc('}'), None)?;
-                                    exps.insert(string.len(), exp);
-                                    string.push(' ');
-                                },
-                                '\\' => is_prev_escape = true,
-                                _ => {
-                                    is_prev_escape = false;
-                                    string.push(ch);
-                                }
-                            }
-                        },
-                        None => {
-                            return Err(err!(END_OF_STR, token.range.end_with(&self.tokens.last_loc)));
-                        }
-                    }
-                };
-                if exps.len() == 0 {
-                    return Err(err!(POINTLESS_TEMPLATE, token.range.end_with(&self.tokens.last_loc)));
-                }
-                ASTExpression::TempStr(ASTTempStr {
-                    template: string,
-                    values: exps,
-                    range: token.range.end_with(&self.tokens.last_loc)
-                })
-            },
-            TokenType::Op(value) => {
-                // Prefixes
-                match self.tokens.parse_full_op(Some(value)).as_str() {
-                    "-" | "!" | "~" => {
-                        let val = if let Some(val) = self.parse_expression_part(parse_generics_in_suffix)? { Box::from(val) } else {
-                            return Err(err!(EXPECTED, token.range,  "expression"));
-                        };
-                        ASTExpression::Unary(
-                            ASTUnary {
-                                op: value.to_string(),
-                                value: val,
-                                range: token.range
-                            }
-                        )
-                    },
-                    val @ ".." | val @ "..=" => ASTExpression::Iterator(ASTIterator {
-                            start: Box::from(ASTExpression::Int(ASTInt { value: 0, range: token.range.clone() })),
-                            end: if let Some(exp) = self.parse_expression()? { Box::from(exp) } else {
-                                return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
-                            },
-                            inclusive: val == "..=",
-                            range: token.range.end_with(&self.tokens.last_loc)
-                    }),
-                    "..." => {
-                        ASTExpression::Spread(
-                            ASTSpread {
-                                value: if let Some(exp) = self.parse_expression()? { Box::from(exp) } else {
-                                    return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
-                                },
-                                range: token.range.end_with(&self.tokens.last_loc)
-                            }
-                        )
-                    }
-                    val @ _ => {
-                        return Err(err!(UNEXPECTED_OP, token.range.end_with(&self.tokens.last_loc),  val));
-                    }
-                }
-            },
+            TokenType::TempStrStart => ASTExpression::TempStr(self.parse_template_literal(token.range)?),
+            TokenType::Op(value) => self.parse_prefix_expression(token.range, value, parse_generics_in_suffix)?,
             TokenType::Punc(val) => {
                 match val {
                     '(' => {
@@ -802,7 +1199,7 @@ impl<'a> Parser<'a> {
                             return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
                         };
                         self.tokens.skip_or_err(TokenType::Punc(')'), Some(err!(EXPECTED, self.tokens.range_here(),  "end of wrapped expression")))?;
-                        exp   
+                        exp
                     },
                     ';' => return Ok(None),
                     '{' => ASTExpression::Block(self.parse_block(true)?),
@@ -818,194 +1215,13 @@ impl<'a> Parser<'a> {
                 }
             },
             TokenType::Kw(val) => {
-                match val.as_str() {
-                    "let" | "const" => {
-                        let is_const = val.as_str() == "const";
-                        let to_get_name = if let Some(n) = self.tokens.consume() {
-                            n 
-                        } else {
-                            return Err(err!(EXPECTED, token.range.end_with(&self.tokens.last_loc),  "variable name"));
-                        };
-                        let var = match to_get_name.val {
-                            TokenType::Punc('[') => ASTDeclareTypes::TupleDeconstruct(self.parse_varname_list(']')?),
-                            TokenType::Punc('{') => ASTDeclareTypes::StructDeconstruct(self.parse_varname_list('}')?),
-                            TokenType::Var(v) => ASTDeclareTypes::Var(ASTVar { value: v, range: to_get_name.range  }),
-                            _ => {
-                                return Err(err!(EXPECTED_FOUND, to_get_name.range,  "identifier or deconstruct pattern"));
-                            }
-                        };
-                        let typings = if self.tokens.is_next(TokenType::Punc(':')) {
-                            self.tokens.consume();
-                            Some(self.parse_typing(false, true, true)?)
-                        } else { None };
-                        let value = if self.tokens.is_next(TokenType::Op('=')) {
-                            self.tokens.consume(); // Skip =
-                            if let Some(exp) = self.parse_expression()? { Some(Box::from(exp)) } else {
-                                return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
-                            }
-                        } else { 
-                            if is_const {
-                                return Err(err!(CONST_WITHOUT_INIT, token.range.end_with(&self.tokens.last_loc)));
-                            }
-                            None
-                         };
-                        return Ok(Some(ASTExpression::Declare(
-                            ASTDeclare {
-                                var,
-                                is_const,
-                                typings,
-                                value,
-                                range: token.range.end_with(&self.tokens.last_loc)
-                            }
-                        )))
-                        },
-                    "fn" => ASTExpression::Function(self.parse_function(true)?),
-                    "if" => {
-                        let condition = if let Some(exp) = self.parse_expression()? { Box::from(exp) } else {
-                            return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
-                        };
-                        let then = if let Some(exp) = self.parse_expression_or_expression_statement()? { Box::from(exp) } else {
-                            return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
-                        };
-                        let otherwise = if self.tokens.is_next(TokenType::Kw(String::from("else"))) {
-                             self.tokens.consume();
-                             if let Some(exp) = self.parse_expression_or_expression_statement()? { Some(Box::from(exp)) } else {
-                                return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
-                            }
-                        } else { None };
-                        return Ok(Some(ASTExpression::If(
-                            ASTIf {
-                                condition,
-                                then,
-                                otherwise,
-                                range: token.range.end_with(&mut self.tokens.last_loc)
-                            }
-                        )))
-                    },
-                    "for" => {
-                        let var = self.parse_varname(false, false, false, false)?.0;
-                        self.tokens.skip_or_err(TokenType::Kw(String::from("in")), None)?;
-                        let iterator = if let Some(exp) = self.parse_expression()? { Box::from(exp) } else {
-                            return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
-                        };
-                        let turn_off_exp_statements = !self.allow_exp_statements;
-                        self.allow_exp_statements = true;
-                        let body = if let Some(exp) = self.parse_expression_or_expression_statement()? { Box::from(exp) } else {
-                            return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
-                        };
-                        if turn_off_exp_statements { self.allow_exp_statements = false; }
-                        return Ok(Some(ASTExpression::ForIn(
-                            ASTForIn {
-                                var,
-                                iterable: iterator,
-                                body,
-                                range: token.range.end_with(&self.tokens.last_loc)
-                            }
-                        )))
-                    },
-                    "while" => {
-                        let cond = if let Some(exp) = self.parse_expression()? { Box::from(exp) } else {
-                            return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
-                        };
-                        let turn_off_exp_statements = !self.allow_exp_statements;
-                        self.allow_exp_statements = true;
-                        let body = if let Some(exp) = self.parse_expression_or_expression_statement()? { Box::from(exp) } else {
-                            return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
-                        };
-                        if turn_off_exp_statements { self.allow_exp_statements = false; }
-                        return Ok(Some(ASTExpression::While(
-                            ASTWhile {
-                                condition: cond,
-                                body,
-                                range: token.range.end_with(&self.tokens.last_loc)
-                            }
-                        )))
-                    },
-                    "match" => {
-                        let to_get_matched = if let Some(exp) = self.parse_expression()? { Box::from(exp) } else {
-                            return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
-                        };
-                        self.tokens.skip_or_err(TokenType::Punc('{'), None)?;
-                        let mut arms: Vec<ASTMatchArm> = vec![];
-                        while !self.tokens.is_next(TokenType::Punc('}')) {
-                            let match_arm_start = self.tokens.input.loc();
-                            let mut possibilities: Vec<ASTMatchArmExpressions> = vec![];
-                            possibilities.push(self.parse_match_arm_exp()?);
-                            if self.tokens.is_next(TokenType::Op('|')) {
-                                self.tokens.consume();
-                                while !self.tokens.is_next(TokenType::Op('=')) && !self.tokens.is_next(TokenType::Kw(String::from("if")))  {
-                                    possibilities.push(self.parse_match_arm_exp()?);
-                                    if self.tokens.is_next(TokenType::Op('|')) { self.tokens.consume(); };
-                                }
-                            }
-                            let guard = if self.tokens.is_next(TokenType::Kw(String::from("if"))) {
-                                self.tokens.consume();
-                                if let Some(exp) = self.parse_expression()? { Some(exp) } else {
-                                    return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
-                                }
-                            } else { None };
-
-                            self.tokens.skip_or_err_full_op("=>", None)?;
-
-                            let body = if let Some(exp) = self.parse_expression()? { exp } else {
-                                return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
-                            };
-                            if self.tokens.is_next(TokenType::Punc(',')) { self.tokens.consume(); };
-                            arms.push(ASTMatchArm {
-                                guard,
-                                possibilities,
-                                body,
-                                range: match_arm_start.end(&self.tokens.last_loc)
-                            });
-                        }
-                        self.tokens.skip_or_err(TokenType::Punc('}'), None)?;
-                        self.is_last_block = true;
-                        return Ok(Some(ASTExpression::Match(ASTMatch {
-                            arms,
-                            range: token.range.end_with(&self.tokens.last_loc),
-                            expression: to_get_matched
-                        })))
-                    },
-                    "new" => {
-                        let target = self.parse_mod_access_or_var_without_var(false, true)?;
-                        let typings = if self.tokens.is_next(TokenType::Op('<')) {
-                            self.tokens.consume();
-                            Some(self.parse_typing_list(false, false, TokenType::Op('>'))?)
-                        } else { None };
-                        self.tokens.skip_or_err(TokenType::Punc('{'), Some(err!(EXPECTED, self.tokens.range_here(),  "struct initializor")))?;
-                        ASTExpression::Init(
-                            ASTInitializor {
-                                target,
-                                params: self.parse_pair_list(true, '}')?,
-                                typings,
-                                range: token.range.end_with(&self.tokens.last_loc)
-                            }
-                        )
-                    },
-                    "await" => {
-                        let optional = if self.tokens.is_next(TokenType::Op('?')) {
-                            self.tokens.consume();
-                            true 
-                        } else { false };
-                        let expression = if let Some(exp) = self.parse_expression()? { Box::from(exp) } else {
-                            return Err(err!(EXPECTED, self.tokens.range_here(),  "expression"));
-                        };
-                        ASTExpression::Await(
-                            ASTAwait {
-                                optional,
-                                expression,
-                                range: token.range.end_with(&self.tokens.last_loc)
-                            }
-                        )
-                    }
-                    _ => {
-                        return Err(err!(EXPECTED_FOUND, token.range,  &format!("keyword \"{}\"", val)));
-                    }
-                }
+                let (exp, parse_suffix) = self.parse_keyword_expression(token.range, &val)?;
+                if !parse_suffix { return Ok(Some(exp)) };
+                exp
             }
         }
         };
-        Ok(Some(self.parse_suffix(exp, parse_generics_in_suffix)?))
+        Ok(Some(self.parse_suffix(exp)?))
     }
 
     fn parse_expression(&mut self) -> LazyResult<Option<ASTExpression>> {
@@ -1100,6 +1316,9 @@ impl<'a> Parser<'a> {
                        ))
                    },
                    "main" => {
+                       if self.script_mode {
+                           return Err(err!(DISALLOWED, token.range, "Main entry"; ["Script files are executed from top to bottom, so they cannot have a main entry. Move the contents of the main block to the top level."]));
+                       }
                        if self.parsed_main {
                            return Err(err!(MANY_ENTRIES, range.end(&self.tokens.last_loc)));
                        };
@@ -1114,7 +1333,7 @@ impl<'a> Parser<'a> {
                        ))
                    },
                    "static" => {
-                       let varname = self.parse_varname(false, false, false, false)?;
+                       let varname = self.parse_varname(true, false, false, false)?;
                        self.tokens.skip_or_err(TokenType::Op('='), None)?;
                        let typings = if let Some(typing) = varname.1 {
                         let len = typing.entries.len();
@@ -1234,8 +1453,10 @@ impl<'a> Parser<'a> {
                             self.tokens.skip_or_err(TokenType::Punc(','), None)?;
                         }
                         if self.tokens.is_next(TokenType::Punc(')')) { break; };
-
-                        args.push(self.tokens.consume().unwrap().val);
+                        let arg = if let Some(tok) = self.tokens.consume() { tok } else {
+                            return Err(err!(UNEXPECTED_EOF, self.tokens.range_here()));
+                        };
+                        args.push(arg.val);
                         is_first = false;
                     }
                     self.tokens.skip_or_err(TokenType::Punc(')'), None)?;
@@ -1258,13 +1479,130 @@ impl<'a> Parser<'a> {
 
     pub fn parse(&mut self) -> Vec<ASTStatement> {
         let mut res = vec![];
-        while !self.tokens.input.is_eof() {
+        if self.parse_script_attribute() { self.script_mode = true };
+        let script_start = self.tokens.input.loc();
+        let mut script_body: Vec<ASTExpression> = vec![];
+        while !self.is_eof() {
+            if self.script_mode && self.is_expression_next() {
+                match self.parse_script_expression() {
+                    Ok(Some(exp)) => script_body.push(exp),
+                    Ok(None) => {},
+                    Err(error) => self.tokens.errors.push(error)
+                }
+                continue;
+            }
             match self.parse_statement() {
                 Ok(stmt) => res.push(stmt),
                 Err(error) => self.tokens.errors.push(error)
             }
         }
+        if self.script_mode {
+            let range = script_body.first().map_or(script_start, |exp| utils::full_expression_range(exp).start).end(&self.tokens.last_loc);
+            res.push(ASTStatement::Main(ASTMain {
+                expression: ASTBlock { elements: script_body, range },
+                range
+            }));
+        }
         res
     }
 
-}
\ No newline at end of file
+    // A `#script` attribute at the very top of the file turns on script mode
+    fn parse_script_attribute(&mut self) -> bool {
+        if !self.tokens.is_next(TokenType::Punc('#')) { return false };
+        let name: String = (0..6).filter_map(|ind| self.tokens.input.peek(ind)).collect();
+        if name != "script" || matches!(self.tokens.input.peek(6), Some('a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '(')) {
+            return false;
+        }
+        self.tokens.consume();
+        self.tokens.consume();
+        true
+    }
+
+    fn is_expression_next(&mut self) -> bool {
+        match self.tokens.peek() {
+            Some(Token { val: TokenType::Kw(kw), .. }) => !matches!(kw.as_str(), "struct" | "enum" | "type" | "main" | "static" | "export" | "import" | "impl"),
+            Some(Token { val: TokenType::Punc('#'), .. }) => false,
+            Some(_) => true,
+            None => false
+        }
+    }
+
+    fn parse_script_expression(&mut self) -> LazyResult<Option<ASTExpression>> {
+        let exp = if let Some(exp) = self.parse_expression()? { exp } else {
+            return Ok(None);
+        };
+        // The last expression of a script doesn't need a semicolon
+        if !self.is_last_block && !self.is_eof() {
+            let range = utils::full_expression_range(&exp);
+            self.tokens.skip_or_err(TokenType::Punc(';'), Some(err!(SEMICOLON, range).with_suggestion("Add a semicolon", range.end.to_range(), ";", Applicability::MaybeIncorrect)))?;
+        }
+        Ok(Some(exp))
+    }
+
+    // Skips whitespace, comments and invalid characters (which are reported by the tokenizer)
+    fn is_eof(&mut self) -> bool {
+        while self.tokens.peek().is_none() {
+            if self.tokens.input.is_eof() { return true };
+        }
+        false
+    }
+
+    fn expect_eof(&mut self) -> LazyResult<()> {
+        if self.is_eof() { return Ok(()) };
+        let tok = self.tokens.peek().unwrap();
+        let range = tok.range;
+        let found = tok.val.to_string();
+        Err(err!(EXPECTED_FOUND, range, "end of input", &found))
+    }
+
+}
+
+fn parse_whole<T, F: FnOnce(&mut Parser) -> LazyResult<Option<T>>>(source: &str, max_depth: usize, parse_fn: F) -> (Option<T>, Vec<BaseError>) {
+    let mut errors = ErrorCollector::new(FileId::default());
+    let mut parser = Parser::new(source, &mut errors);
+    parser.max_depth = max_depth;
+    let result = parse_fn(&mut parser).and_then(|val| {
+        parser.expect_eof()?;
+        Ok(val)
+    });
+    let value = match result {
+        Ok(val) => val,
+        Err(error) => {
+            parser.tokens.errors.push(error);
+            None
+        }
+    };
+    (value, errors.collected)
+}
+
+// Parses a single expression. A trailing semicolon is allowed, anything else after the expression is an error.
+pub fn parse_expression_str(source: &str) -> (Option<ASTExpression>, Vec<BaseError>) {
+    parse_expression_str_with_depth(source, DEFAULT_MAX_DEPTH)
+}
+
+pub fn parse_expression_str_with_depth(source: &str, max_depth: usize) -> (Option<ASTExpression>, Vec<BaseError>) {
+    parse_whole(source, max_depth, |parser| {
+        let exp = parser.parse_expression()?;
+        if exp.is_some() && parser.tokens.is_next(TokenType::Punc(';')) { parser.tokens.consume(); };
+        Ok(exp)
+    })
+}
+
+// Parses a single typing, for example `Vec<i32>?` or `{ name: str } + Stringable`
+pub fn parse_type_str(source: &str) -> (Option<ASTTypings>, Vec<BaseError>) {
+    parse_type_str_with_depth(source, DEFAULT_MAX_DEPTH)
+}
+
+pub fn parse_type_str_with_depth(source: &str, max_depth: usize) -> (Option<ASTTypings>, Vec<BaseError>) {
+    parse_whole(source, max_depth, |parser| Ok(Some(parser.parse_typing(false, true, true)?)))
+}
+
+// Parses a list of top-level statements - the same thing as `Parser::parse`, but without needing an error collector.
+pub fn parse_statements_str(source: &str) -> (Vec<ASTStatement>, Vec<BaseError>) {
+    parse_statements_str_with_depth(source, DEFAULT_MAX_DEPTH)
+}
+
+pub fn parse_statements_str_with_depth(source: &str, max_depth: usize) -> (Vec<ASTStatement>, Vec<BaseError>) {
+    let (statements, errors) = parse_whole(source, max_depth, |parser| Ok(Some(parser.parse())));
+    (statements.unwrap_or_default(), errors)
+}
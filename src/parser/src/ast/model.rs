@@ -10,9 +10,39 @@ pub struct ASTStr {
     pub range: Range
 }
 
+#[derive(Clone, Copy, PartialEq)]
+pub enum ASTFormatAlign {
+    Left,
+    Right,
+    Center
+}
+
+// The part after the colon in `${value:>8.2}`
+pub struct ASTFormatSpec {
+    pub fill: Option<char>,
+    pub align: Option<ASTFormatAlign>,
+    pub sign: bool,
+    pub alternate: bool,
+    pub zero_pad: bool,
+    pub width: Option<usize>,
+    pub precision: Option<usize>,
+    pub kind: Option<char>,
+    pub range: Range
+}
+
+pub struct ASTTempStrInterpolation {
+    pub value: ASTExpression,
+    pub format: Option<ASTFormatSpec>,
+    pub range: Range
+}
+
+pub enum ASTTempStrPart {
+    Literal(ASTStr),
+    Interpolation(ASTTempStrInterpolation)
+}
+
 pub struct ASTTempStr {
-    pub template: String,
-    pub values: HashMap<usize, ASTExpression>,
+    pub parts: Vec<ASTTempStrPart>,
     pub range: Range
 }
 
@@ -40,14 +70,24 @@ pub struct ASTVar {
     pub range: Range
 }
 
-pub struct ASTVarList {
-    pub values: Vec<ASTVar>,
+// A single element of a deconstruct pattern
+// `field` is only present in struct patterns - it's the name of the property being deconstructed
+pub struct ASTDeconstructItem {
+    pub field: Option<ASTVar>,
+    pub pattern: ASTDeclareTypes,
+    pub default_value: Option<ASTExpression>,
+    pub spread: bool,
+    pub range: Range
+}
+
+pub struct ASTDeconstructList {
+    pub values: Vec<ASTDeconstructItem>,
     pub range: Range
 }
 
 pub enum ASTDeclareTypes {
-    TupleDeconstruct(ASTVarList),
-    StructDeconstruct(ASTVarList),
+    TupleDeconstruct(ASTDeconstructList),
+    StructDeconstruct(ASTDeconstructList),
     Var(ASTVar)
 }
 
@@ -81,7 +121,8 @@ pub struct ASTStruct {
 
 // A key value pair list
 pub struct ASTPairList {
-    pub pairs: Vec<(String, Option<ASTExpression>)>,
+    // The name, value and name range of every pair
+    pub pairs: Vec<(String, Option<ASTExpression>, Range)>,
     pub range: Range
 }
 
@@ -337,6 +378,16 @@ pub enum ASTStatement {
     Impl(ASTImpl)
 }
 
+impl ASTModAccessValues {
+
+    pub fn range(&self) -> Range {
+        match self {
+            Self::ModAccess(access) => access.range,
+            Self::Var(var) => var.range
+        }
+    }
+}
+
 impl ASTStatement {
     
     pub fn range(&self) -> Range {
@@ -370,6 +421,7 @@ impl ASTModifiers {
 
 pub struct ASTPairTypingItem {
     pub name: String,
+    pub name_range: Range,
     pub value: Option<ASTTypings>,
     pub spread: bool,
     pub default_value: Option<ASTExpression>,
@@ -783,7 +835,18 @@ impl fmt::Display for ASTImpl {
    }
 }
 
-impl fmt::Display for ASTVarList {
+impl fmt::Display for ASTDeconstructItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pattern = match (&self.field, &self.pattern) {
+            (Some(field), ASTDeclareTypes::Var(v)) if field.value == v.value => v.to_string(),
+            (Some(field), pattern) => format!("{}: {}", field, pattern),
+            (None, pattern) => pattern.to_string()
+        };
+        write!(f, "{}{}{}", if self.spread { "..." } else { "" }, pattern, if let Some(default) = &self.default_value { format!(" = {}", default) } else { String::from("") })
+   }
+}
+
+impl fmt::Display for ASTDeconstructList {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.values.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(", "))
    }
@@ -811,15 +874,42 @@ impl fmt::Display for ASTIndexAccess {
    }
 }
 
+impl fmt::Display for ASTFormatSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut spec = String::new();
+        if let Some(fill) = self.fill { spec.push(fill) };
+        if let Some(align) = self.align {
+            spec.push(match align {
+                ASTFormatAlign::Left => '<',
+                ASTFormatAlign::Right => '>',
+                ASTFormatAlign::Center => '^'
+            });
+        };
+        if self.sign { spec.push('+') };
+        if self.alternate { spec.push('#') };
+        if self.zero_pad { spec.push('0') };
+        if let Some(width) = self.width { spec.push_str(&width.to_string()) };
+        if let Some(precision) = self.precision { spec.push_str(&format!(".{}", precision)) };
+        if let Some(kind) = self.kind { spec.push(kind) };
+        write!(f, "{}", spec)
+   }
+}
+
 impl fmt::Display for ASTTempStr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut new_str = String::new();
-        for (ind, ch) in self.template.chars().enumerate() {
-            if let Some(k) = self.values.get(&ind) {
-                new_str.push_str(&format!("${{{}}}", k));
-            } else {
-                new_str.push(ch);
-            };
+        for part in &self.parts {
+            match part {
+                ASTTempStrPart::Literal(lit) => {
+                    for ch in lit.value.chars() {
+                        if ch == '`' || ch == '$' || ch == '\\' { new_str.push('\\') };
+                        new_str.push(ch);
+                    }
+                },
+                ASTTempStrPart::Interpolation(int) => {
+                    new_str.push_str(&format!("${{{}{}}}", int.value, if let Some(spec) = &int.format { format!(":{}", spec) } else { String::from("") }));
+                }
+            }
         }
         write!(f, "`{}`", new_str)
    }
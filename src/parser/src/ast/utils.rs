@@ -1,45 +1,62 @@
 use super::*;
 
+// The range of the expression node itself, without taking its children into account
+fn own_expression_range(ast: &ASTExpression) -> Range {
+    match ast {
+        ASTExpression::Var(v) => v.range,
+        ASTExpression::Str(v) => v.range,
+        ASTExpression::Bool(v) => v.range,
+        ASTExpression::Float(v) => v.range,
+        ASTExpression::Int(v) => v.range,
+        ASTExpression::Binary(bin) => bin.range,
+        ASTExpression::Unary(un) => un.range,
+        ASTExpression::DotAccess(access) => access.range,
+        ASTExpression::IndexAccess(access) => access.range,
+        ASTExpression::Block(block) => block.range,
+        ASTExpression::Declare(l) => l.range,
+        ASTExpression::Init(init) => init.range,
+        ASTExpression::Optional(op) => op.range,
+        ASTExpression::Function(fun) => fun.range,
+        ASTExpression::Iterator(init) => init.range,
+        ASTExpression::Call(call) => call.range,
+        ASTExpression::ForIn(for_in) => for_in.range,
+        ASTExpression::While(while_loop) => while_loop.range,
+        ASTExpression::If(ifexp) => ifexp.range,
+        ASTExpression::Char(ch) => ch.range,
+        ASTExpression::ModAccess(e) => e.range,
+        ASTExpression::Tuple(tup) => tup.range,
+        ASTExpression::Yield(y) => y.range,
+        ASTExpression::Spread(sp) => sp.range,
+        ASTExpression::Match(mtch) => mtch.range,
+        ASTExpression::Await(aw) => aw.range,
+        ASTExpression::TempStr(tmp) => tmp.range,
+        ASTExpression::None(range) => *range
+    }
+}
+
+// Both the start and the end are found iteratively, so very long operator or suffix chains don't overflow the stack
 pub fn full_expression_range(ast: &ASTExpression) -> Range {
-            match ast {
-                ASTExpression::Var(v) => v.range,
-                ASTExpression::Str(v) => v.range,
-                ASTExpression::Bool(v) => v.range,
-                ASTExpression::Float(v) => v.range,
-                ASTExpression::Int(v) => v.range,
-                ASTExpression::Binary(bin) => Range{start: full_expression_range(&bin.left).start, end: full_expression_range(&bin.right).end},
-                ASTExpression::Unary(un) => Range { start: un.range.start, end: full_expression_range(&un.value).end },
-                ASTExpression::DotAccess(access) => Range { start:  full_expression_range(&access.value).start, end: access.range.end },
-                ASTExpression::IndexAccess(access) => Range { start: full_expression_range(&access.value).start, end: access.range.end },
-                ASTExpression::Block(block) => block.range,
-                ASTExpression::Declare(l) => l.range,
-                ASTExpression::Init(init) => init.range,
-                ASTExpression::Optional(op) => {
-                    let start = full_expression_range(&op.value).start;
-                    Range { start, end: op.range.end }
-                },
-                ASTExpression::Function(fun) => fun.range,
-                ASTExpression::Iterator(init) => {
-                    let start = full_expression_range(&init.start).start;
-                    Range { start, end: init.range.end }
-                },
-                ASTExpression::Call(call) => {
-                    let start = full_expression_range(&call.target).start;
-                    Range { start, end: call.range.end }
-                },
-                ASTExpression::ForIn(for_in) => for_in.range,
-                ASTExpression::While(while_loop) => while_loop.range,
-                ASTExpression::If(ifexp) => ifexp.range,
-                ASTExpression::Char(ch) => ch.range,
-                ASTExpression::ModAccess(e) => e.range,
-                ASTExpression::Tuple(tup) => tup.range,
-                ASTExpression::Yield(y) => y.range,
-                ASTExpression::Spread(sp) => sp.range,
-                ASTExpression::Match(mtch) => mtch.range,
-                ASTExpression::Await(aw) => aw.range,
-                ASTExpression::TempStr(tmp) => tmp.range,
-                ASTExpression::None(range) => *range
-        }
+    let mut leftmost = ast;
+    loop {
+        leftmost = match leftmost {
+            ASTExpression::Binary(bin) => &bin.left,
+            ASTExpression::DotAccess(access) => &access.value,
+            ASTExpression::IndexAccess(access) => &access.value,
+            ASTExpression::Optional(op) => &op.value,
+            ASTExpression::Iterator(iter) => &iter.start,
+            ASTExpression::Call(call) => &call.target,
+            _ => break
+        };
+    }
+    let mut rightmost = ast;
+    loop {
+        rightmost = match rightmost {
+            ASTExpression::Binary(bin) => &bin.right,
+            ASTExpression::Unary(un) => &un.value,
+            _ => break
+        };
+    }
+    Range { start: own_expression_range(leftmost).start, end: own_expression_range(rightmost).end }
 }
 
 pub fn is_natural_iter(ast: &ASTIterator) -> bool {
@@ -1,20 +1,22 @@
 
-use errors::{LoC};
+use errors::{LoC, FileId};
 
 pub struct InputParser {
     code: Vec<char>,
     pub line: usize,
     pub col: usize,
-    pos: usize
+    pos: usize,
+    file: FileId
 }
 
 impl InputParser {
 
-    pub fn new(code: &str) -> Self {
+    pub fn new(code: &str, file: FileId) -> Self {
         InputParser {
             line: 1,
             col: 0,
             pos: 0,
+            file,
             code: code.chars().collect()
         }
     }
@@ -43,18 +45,15 @@ impl InputParser {
         self.pos >= self.code.len()
     }
 
+    // Skips to the start of the next line, or to the end of the input if this is the last line
     pub fn skip_line(&mut self) {
-        if self.is_eof() { return; };
-        while self.code[self.pos] != '\n' {
-            self.pos += 1;
+        while let Some(char) = self.consume() {
+            if char == '\n' { break };
         }
-        self.pos += 1;
-        self.col = 0;
-        self.line += 1;
     }
 
     pub fn loc(&self) -> LoC {
-        LoC { line: self.line, col: self.col, pos: self.pos }
+        LoC { line: self.line, col: self.col, pos: self.pos, file: self.file }
     }
     
 }
\ No newline at end of file
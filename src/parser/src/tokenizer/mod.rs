@@ -70,12 +70,13 @@ pub struct Tokenizer<'a> {
 impl<'a> Tokenizer<'a> {
 
     pub fn new(code: &str, errors: &'a mut ErrorCollector) -> Self {
+        let file = errors.file;
         Tokenizer {
             current: None,
             errors,
             is_last_num_as_str: false,
-            input: InputParser::new(code),
-            last_loc: LoC::default()
+            input: InputParser::new(code, file),
+            last_loc: LoC { file, ..LoC::default() }
         }
     }
 
@@ -315,8 +316,9 @@ impl<'a> Tokenizer<'a> {
             ch => {
                 let loc = self.input.loc();
                 self.input.consume();
-                if let Some(confused_err) = Self::is_confusable(ch) {
-                    self.errors.push(BaseError::new(confused_err, loc.to_range()));
+                if let Some((found, expected, replacement)) = Self::is_confusable(ch) {
+                    let range = loc.end(&self.input.loc());
+                    self.errors.push(err!(CONFUSABLE, loc.to_range(), found, expected).with_suggestion("Replace the character", range, replacement, Applicability::MachineApplicable));
                     return None;
                 };
                 self.errors.push(err!(INVALID_CHAR, loc.to_range(), &ch.to_string()));
@@ -350,7 +352,7 @@ impl<'a> Tokenizer<'a> {
         let next = self.peek();
         match next {
             Some(token) => token.val == tok,
-            None => true
+            None => false
         }
     }
 
@@ -431,17 +433,17 @@ impl<'a> Tokenizer<'a> {
         }
     }
 
-    pub fn is_confusable(ch: char) -> Option<String> {
+    pub fn is_confusable(ch: char) -> Option<(&'static str, &'static str, &'static str)> {
         match ch {
-            ';' => Some(dia!(CONFUSABLE, "; (Greek question mark)", "; (semicolon)")),
-            '‚' => Some(dia!(CONFUSABLE, "‚ (low-9 quatation mark)", ", (comma)")),
-            '٫' => Some(dia!(CONFUSABLE, "‚ (arabic decimal separator)", ", (comma)")),
-            '：' => Some(dia!(CONFUSABLE, "： (fullwidth colon)", ": (colon)")),
-            '։' => Some(dia!(CONFUSABLE, "： (armenian full stop)", ": (colon)")),
-            '∶' => Some(dia!(CONFUSABLE, "∶ (ratio)", ": (colon)")),
-            '！' => Some(dia!(CONFUSABLE, "！ (fullwidth exclamation mark)", "! (exclamation mark)")),
-            'ǃ' => Some(dia!(CONFUSABLE, "ǃ (latin letter retroflex click)", "! (exclamation mark)")),
-            '․' => Some(dia!(CONFUSABLE, "․ (one dot leader)", ". (full stop)")),
+            ';' => Some(("; (Greek question mark)", "; (semicolon)", ";")),
+            '‚' => Some(("‚ (low-9 quatation mark)", ", (comma)", ",")),
+            '٫' => Some(("‚ (arabic decimal separator)", ", (comma)", ",")),
+            '：' => Some(("： (fullwidth colon)", ": (colon)", ":")),
+            '։' => Some(("： (armenian full stop)", ": (colon)", ":")),
+            '∶' => Some(("∶ (ratio)", ": (colon)", ":")),
+            '！' => Some(("！ (fullwidth exclamation mark)", "! (exclamation mark)", "!")),
+            'ǃ' => Some(("ǃ (latin letter retroflex click)", "! (exclamation mark)", "!")),
+            '․' => Some(("․ (one dot leader)", ". (full stop)", ".")),
             _ => None
         }
     }
@@ -1,13 +1,39 @@
 use errors::*;
 use crate::{module::*, symbol::*};
+use parser::ast::utils::full_expression_range;
 use rustc_hash::FxHashMap;
 
+// The built-in types. They're created before any other symbol, so their ids are always the same.
+pub const PRIMITIVES: &[&str] = &["i8", "i16", "i32", "u8", "u16", "u32", "f32", "char", "str", "bool", "none"];
+// The primitives which integer literals can be assigned to
+const NUMBERS: &[&str] = &["i8", "i16", "i32", "u8", "u16", "u32", "f32"];
+
+// An impl block which was checked
+struct CheckedImpl {
+    // The declaration of the partial, which is the same for all of its instances
+    partial_id: u32,
+    partial: SymbolRef,
+    target: SymbolRef,
+    id: u32
+}
+
 pub struct TypeChecker {
-    pub symbols: FxHashMap<u32, Symbol>
+    pub symbols: FxHashMap<u32, Symbol>,
+    pub primitives: HashMap<String, u32>,
+    id_counter: u32,
+    // The type parameters which are visible in the declaration that's being checked
+    type_params: HashMap<String, SymbolRef>,
+    // The type aliases which are being resolved, an alias which refers to one of them is cyclic
+    alias_stack: Vec<(u32, String)>,
+    impls: Vec<CheckedImpl>,
+    // The variables of the bodies which are being checked, innermost scope last. It's empty while signatures are checked.
+    values: Vec<HashMap<String, SymbolRef>>,
+    // Statics without a type annotation which are being checked, they can't refer to themselves
+    static_stack: Vec<u32>
 }
 
 impl SymbolCollector for TypeChecker {
-    
+
     fn get_symbol(&self, name: &u32) -> Option<&Symbol> {
         self.symbols.get(name)
     }
@@ -22,92 +48,1452 @@ impl SymbolCollector for TypeChecker {
 
 }
 
+impl Default for TypeChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TypeChecker {
 
-    pub fn check_module(&mut self, module: &mut Module) -> LazyResult<()> {
+    pub fn new() -> Self {
+        let mut checker = Self {
+            symbols: FxHashMap::default(),
+            primitives: HashMap::new(),
+            id_counter: 0,
+            type_params: HashMap::new(),
+            alias_stack: Vec::new(),
+            impls: Vec::new(),
+            values: Vec::new(),
+            static_stack: Vec::new()
+        };
+        for name in PRIMITIVES {
+            let reference = checker.create_symbol(name.to_string(), SymbolKind::Primitive);
+            checker.primitives.insert(name.to_string(), reference.id);
+        }
+        checker
+    }
+
+    pub fn get_unique_id(&mut self) -> u32 {
+        self.id_counter += 1;
+        self.id_counter
+    }
+
+    pub fn get_primitive(&self, name: &str) -> SymbolRef {
+        SymbolRef::new_ref(self.primitives[name])
+    }
+
+    // Creates a symbol which isn't declared by a statement - primitives, type parameters, and types without a name, like tuples
+    fn create_symbol(&mut self, name: String, kind: SymbolKind) -> SymbolRef {
+        let id = self.get_unique_id();
+        let mut sym = Symbol::empty(id, name, StatementOrExpression::None);
+        sym.kind = kind;
+        self.insert_symbol(sym);
+        SymbolRef::new_ref(id)
+    }
+
+    // Checks all declarations of the module, in the order they're declared in
+    pub fn check_module(&mut self, module: &mut Module, errors: &mut ErrorCollector) {
+        // Implementations are registered first, because they change which partials the declarations fit
+        for implementation in std::mem::take(&mut module.impls) {
+            if let Err(err) = self.check_impl(module, implementation) {
+                errors.push(err);
+            }
+        }
+        let mut names = module.temporary.iter().map(|(name, sym)| (sym.declaration.name_range().map_or(0, |range| range.start.pos), name.clone())).collect::<Vec<(usize, String)>>();
+        names.sort();
+        for (_, name) in names {
+            // Declarations which are used by an earlier declaration are already checked
+            if !module.temporary.contains_key(&name) { continue };
+            if let Err(err) = self.check_declaration(module, &name) {
+                errors.push(err);
+            }
+        }
+        if let Some(main) = module.main.take() {
+            // The value of the main entry isn't used
+            let none = self.get_primitive("none");
+            if let Err(err) = self.check_block(module, &main.expression, Some(&none)) {
+                errors.push(err);
+            }
+            module.main = Some(main);
+        }
+    }
+
+    // Moves the declaration from the temporary symbols of the module to the checker, and checks it
+    fn check_declaration(&mut self, module: &mut Module, name: &str) -> LazyResult<SymbolRef> {
+        let mut sym = module.temporary.remove(name).unwrap();
+        let id = sym.id;
+        let declaration = std::mem::replace(&mut sym.declaration, StatementOrExpression::None);
+        // The symbol is inserted before it's checked, so the declaration can refer to itself
+        self.insert_symbol(sym);
+        let outer_type_params = std::mem::take(&mut self.type_params);
+        // Structs and enums break alias cycles, because they're referred to by name
+        let aliases = if let StatementOrExpression::TypeStatement(_) = &declaration { self.alias_stack.clone() } else { Vec::new() };
+        let outer_aliases = std::mem::replace(&mut self.alias_stack, aliases);
+        let outer_values = std::mem::take(&mut self.values);
+        let kind = match &declaration {
+            StatementOrExpression::StructStatement(structure) => self.check_struct(module, id, structure),
+            StatementOrExpression::EnumStatement(enumeration) => self.check_enum(module, id, enumeration),
+            StatementOrExpression::TypeStatement(alias) => self.check_alias(module, id, alias),
+            StatementOrExpression::StaticStatement(decl) => self.check_static(module, id, decl),
+            StatementOrExpression::ImplStatement(_) | StatementOrExpression::None => Ok(SymbolKind::None)
+        };
+        self.type_params = outer_type_params;
+        self.alias_stack = outer_aliases;
+        let result = kind.and_then(|kind| {
+            self.symbols.get_mut(&id).unwrap().kind = kind;
+            // Instances which were created while the declaration was checked don't have a kind yet
+            let mut ind = 0;
+            while ind < self.symbols[&id].instances.len() {
+                if matches!(self.symbols[&id].instances[ind].kind, SymbolKind::None) { self.fill_instance(id, ind)? };
+                ind += 1;
+            }
+            if let StatementOrExpression::StructStatement(structure) = &declaration {
+                let type_params = self.symbols[&id].type_params.iter().map(|param| (param.to_symbol(self).name.clone(), param.clone())).collect();
+                let outer_type_params = std::mem::replace(&mut self.type_params, type_params);
+                let result = self.check_methods(module, id, SymbolRef::new_ref(id), &structure.fields);
+                self.type_params = outer_type_params;
+                result?;
+            }
+            Ok(())
+        });
+        self.values = outer_values;
+        self.symbols.get_mut(&id).unwrap().declaration = declaration;
+        result?;
+        Ok(self.resolve_alias(SymbolRef::new_ref(id)))
+    }
+
+    fn check_impl(&mut self, module: &mut Module, mut sym: Symbol) -> LazyResult<()> {
+        let id = sym.id;
+        let declaration = std::mem::replace(&mut sym.declaration, StatementOrExpression::None);
+        self.insert_symbol(sym);
+        let outer_type_params = std::mem::take(&mut self.type_params);
+        let outer_values = std::mem::take(&mut self.values);
+        let result = match &declaration {
+            StatementOrExpression::ImplStatement(implementation) => self.check_impl_members(module, id, implementation),
+            _ => Ok(())
+        };
+        self.type_params = outer_type_params;
+        self.values = outer_values;
+        self.symbols.get_mut(&id).unwrap().declaration = declaration;
+        result
+    }
+
+    fn check_impl_members(&mut self, module: &mut Module, id: u32, implementation: &ASTImpl) -> LazyResult<()> {
+        // The type parameters of the impl block, like the `T` in `impl<T> Unwrap<T> for Option<T>`
+        for typing in implementation.typings.iter().flat_map(|typings| &typings.entries) {
+            if let ASTTypings::Var(var) = typing {
+                let reference = self.create_symbol(var.value.value.clone(), SymbolKind::TypeParam(None)).make_type_param();
+                self.type_params.insert(var.value.value.clone(), reference.clone());
+                self.symbols.get_mut(&id).unwrap().type_params.push(reference);
+            }
+        }
+        let partial = self.get_sym_from_mod_access_values(module, &implementation.partial)?;
+        let partial_id = match &implementation.partial {
+            ASTModAccessValues::Var(var) => self.get_sym_from_var(module, &var.value)?.id,
+            ASTModAccessValues::ModAccess(_) => partial.id
+        };
+        let target = self.get_sym_from_mod_access_values(module, &implementation.target)?;
+        let (partial_name, target_name) = (implementation.partial.to_string(), implementation.target.to_string());
+        if self.is_anonymous(&target) || !matches!(target.to_symbol(self).kind, SymbolKind::Struct(_) | SymbolKind::Enum(_)) {
+            return Err(err!(INVALID_IMPL_TARGET, implementation.target.range(), &target_name));
+        }
+        let required = self.get_members(&partial).ok_or_else(|| err!(NOT_A_PARTIAL, implementation.partial.range(), &partial_name))?;
+        if let Some(other) = self.impls.iter().find(|other| other.partial_id == partial_id && other.target.id == target.id && ((other.target == target && other.partial == partial) || self.is_blanket(&other.target) || self.is_blanket(&target))) {
+            let mut err = err!(CONFLICTING_IMPL, implementation.target.range(), &partial_name, &target_name);
+            if let Some(range) = self.symbols[&other.id].declaration.name_range() {
+                err = err.with_label("First implemented here", range);
+            }
+            return Err(err);
+        }
+        let props = self.check_fields(module, &implementation.fields)?;
+        self.symbols.get_mut(&id).unwrap().kind = SymbolKind::Struct(props);
+        self.impls.push(CheckedImpl { partial_id, partial, target: target.clone(), id });
+        self.symbols.get_mut(&target.id).unwrap().impls.push(SymbolRef::new_ref(id));
+        for ind in 0..self.symbols[&target.id].instances.len() {
+            self.instantiate_impls(&SymbolRef::new_instance(target.id, ind), implementation.target.range())?;
+        }
+        // The members of the target and of its implementations have to fit the partial
+        let members = self.get_members(&target).unwrap_or_default();
+        let mut required = required.into_iter().collect::<Vec<(String, SymbolRef)>>();
+        required.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (name, expected) in required {
+            match members.get(&name) {
+                None => return Err(err!(MISSING_FIELD, implementation.target.range(), &target_name, &partial_name, &name)),
+                Some(found) if !self.is_assignable(found, &expected) => return Err(err!(WRONG_FIELD_TYPE, implementation.target.range(), &target_name, &partial_name, &name)),
+                _ => {}
+            }
+        }
+        self.check_methods(module, id, target, &implementation.fields)
+    }
+
+    // The bodies of functions in fields are checked once the type of `self` is known
+    fn check_methods(&mut self, module: &mut Module, owner: u32, self_type: SymbolRef, fields: &ASTPairListTyping) -> LazyResult<()> {
+        let props = if let SymbolKind::Struct(props) = &self.symbols[&owner].kind { props.clone() } else { return Ok(()) };
+        self.in_scope(HashMap::from([(String::from("self"), self_type)]), |checker| {
+            for field in &fields.pairs {
+                match (&field.value, &field.default_value) {
+                    (Some(ASTTypings::Function(func)), _) if func.body.is_some() => {
+                        checker.check_function(module, func, false, None)?;
+                    },
+                    (_, Some(default_value @ ASTExpression::Function(_))) => checker.check_exp_assignable(module, default_value, &props[&field.name].kind)?,
+                    _ => {}
+                }
+            }
+            Ok(())
+        })
+    }
+
+    // An annotated static can refer to itself, for example in the body of a recursive function
+    fn check_static(&mut self, module: &mut Module, id: u32, decl: &ASTStatic) -> LazyResult<SymbolKind> {
+        let typing = match decl.typings.as_ref().and_then(|typings| typings.entries.first()) {
+            Some(typing) => Some(self.get_sym_from_type(module, typing)?),
+            None => None
+        };
+        match &typing {
+            Some(typing) => self.symbols.get_mut(&id).unwrap().kind = SymbolKind::Static(typing.clone()),
+            None => self.static_stack.push(id)
+        };
+        let is_annotated = typing.is_some();
+        let typing = self.in_scope(HashMap::new(), |checker| checker.check_initializer(module, Some(&decl.value), typing, &decl.var.value, decl.var.range));
+        if !is_annotated { self.static_stack.pop(); }
+        Ok(SymbolKind::Static(typing?))
+    }
+
+    // The type of a variable comes from the annotation, or from the value if there's no annotation
+    fn check_initializer(&mut self, module: &mut Module, value: Option<&ASTExpression>, typing: Option<SymbolRef>, name: &str, range: Range) -> LazyResult<SymbolRef> {
+        match (typing, value) {
+            (Some(typing), Some(value)) => {
+                self.check_exp_assignable(module, value, &typing)?;
+                Ok(typing)
+            },
+            (Some(typing), None) => Ok(typing),
+            (None, Some(value)) => self.get_sym_from_exp(module, value)?.ok_or_else(|| err!(CANNOT_INFER, full_expression_range(value), name)),
+            (None, None) => Err(err!(CANNOT_INFER, range, name))
+        }
+    }
+
+    // Runs the check with a new scope of variables
+    fn in_scope<T>(&mut self, scope: HashMap<String, SymbolRef>, check: impl FnOnce(&mut Self) -> LazyResult<T>) -> LazyResult<T> {
+        self.values.push(scope);
+        let result = check(self);
+        self.values.pop();
+        result
+    }
+
+    // Generic implementations get an instance for every instance of their target, like `impl<T> Unwrap<T> for Option<T>`
+    // for `Option<i32>`, which has the members of `Unwrap<i32>`
+    fn instantiate_impls(&mut self, reference: &SymbolRef, range: Range) -> LazyResult<()> {
+        let implementations = self.impls.iter().filter(|implementation| implementation.target.id == reference.id).map(|implementation| implementation.id).collect::<Vec<u32>>();
+        for implementation in implementations {
+            if let Some(type_args) = self.get_impl_type_args(implementation, reference) {
+                self.instantiate(implementation, type_args, range)?;
+            }
+        }
+        Ok(())
+    }
+
+    // The type arguments of a generic implementation for an instance of its target
+    fn get_impl_type_args(&self, implementation: u32, reference: &SymbolRef) -> Option<Vec<SymbolRef>> {
+        let target = &self.impls.iter().find(|checked| checked.id == implementation)?.target;
+        let params = &self.symbols[&implementation].type_params;
+        if params.is_empty() || target.instance_id == reference.instance_id || !self.is_blanket(target) {
+            return None;
+        }
+        let target_args = &target.to_symbol(self).instances[target.instance_id?].type_args;
+        let args = &reference.to_symbol(self).instances[reference.instance_id?].type_args;
+        Some(params.iter().map(|param| match target_args.iter().position(|arg| arg.id == param.id) {
+            Some(ind) => args[ind].clone(),
+            None => param.clone()
+        }).collect())
+    }
+
+    // Implementations for the symbol itself or for instances whose type arguments are all type parameters apply to every instance
+    fn is_blanket(&self, target: &SymbolRef) -> bool {
+        target.instance_id.is_none_or(|id| target.to_symbol(self).instances[id].type_args.iter().all(|arg| arg.is_type_param()))
+    }
+
+    fn check_struct(&mut self, module: &mut Module, id: u32, structure: &ASTStruct) -> LazyResult<SymbolKind> {
+        self.declare_type_params(module, id, &structure.typings)?;
+        Ok(SymbolKind::Struct(self.check_fields(module, &structure.fields)?))
+    }
+
+    // Variants without a type hold `none`
+    fn check_enum(&mut self, module: &mut Module, id: u32, enumeration: &ASTEnumDeclaration) -> LazyResult<SymbolKind> {
+        self.declare_type_params(module, id, &enumeration.typings)?;
+        let mut variants: HashMap<String, SymbolRef> = HashMap::new();
+        for (ind, variant) in enumeration.values.pairs.iter().enumerate() {
+            if let Some(first) = enumeration.values.pairs[..ind].iter().find(|other| other.name == variant.name) {
+                return Err(err!(DUPLICATE_VARIANT, variant.name_range, &variant.name).with_label("First defined here", first.name_range));
+            }
+            let payload = match &variant.value {
+                Some(typing) => self.get_sym_from_type(module, typing)?,
+                None => self.get_primitive("none")
+            };
+            variants.insert(variant.name.clone(), payload);
+        }
+        Ok(SymbolKind::Enum(variants))
+    }
+
+    fn check_alias(&mut self, module: &mut Module, id: u32, alias: &ASTType) -> LazyResult<SymbolKind> {
+        self.declare_type_params(module, id, &alias.typings)?;
+        self.alias_stack.push((id, alias.name.value.clone()));
+        let target = self.get_sym_from_type(module, &alias.value)?;
+        self.alias_stack.pop();
+        Ok(SymbolKind::Alias(target))
+    }
+
+    // Aliases are replaced with the type they alias. Generic aliases are replaced once they're given type arguments.
+    fn resolve_alias(&self, reference: SymbolRef) -> SymbolRef {
+        let sym = reference.to_symbol(self);
+        match reference.get_kind(self) {
+            SymbolKind::Alias(target) if reference.instance_id.is_some() || sym.type_params.is_empty() => if reference.is_optional() { target.clone().make_optional() } else { target.clone() },
+            _ => reference
+        }
+    }
+
+    // Instances are memoized by their type arguments, so recursive types like `Node<T> { next: Node<T>? }` end
+    fn instantiate(&mut self, id: u32, type_args: Vec<SymbolRef>, range: Range) -> LazyResult<SymbolRef> {
+        let sym = self.symbols.get_mut(&id).unwrap();
+        let count = sym.instances.len();
+        let instance = sym.create_or_get_instance(type_args, range)?;
+        // The kind of the declaration isn't known yet if it's still being checked, the instance is filled in after it
+        if sym.instances.len() != count {
+            if !matches!(sym.kind, SymbolKind::None) {
+                self.fill_instance(id, instance.instance_id.unwrap())?;
+            }
+            self.instantiate_impls(&instance, range)?;
+        }
+        Ok(instance)
+    }
+
+    // Checks the type arguments against the constraints of the type parameters they're given to
+    fn check_bounds(&mut self, id: u32, type_args: &[SymbolRef], range: Range) -> LazyResult<()> {
+        let params = self.symbols[&id].type_params.clone();
+        let map = params.iter().map(|param| param.id).zip(type_args.iter().cloned()).collect::<Vec<(u32, SymbolRef)>>();
+        for (param, arg) in params.iter().zip(type_args) {
+            let constraint = match &param.to_symbol(self).kind {
+                SymbolKind::TypeParam(Some(constraint)) => constraint.clone(),
+                _ => continue
+            };
+            // Constraints can refer to the type parameters, like `T: { eq: (other: T) -> bool }`
+            let constraint = self.substitute(&constraint, &map)?;
+            let param_name = param.to_symbol(self).name.clone();
+            if let Some(required) = self.get_members(&constraint) {
+                let members = self.get_members(arg).unwrap_or_default();
+                let mut required = required.into_iter().collect::<Vec<(String, SymbolRef)>>();
+                required.sort_by(|(a, _), (b, _)| a.cmp(b));
+                for (name, expected) in required {
+                    match members.get(&name) {
+                        None => return Err(err!(MISSING_BOUND_MEMBER, range, &self.type_name(arg), &param_name, &name)),
+                        Some(found) if !self.is_assignable(found, &expected) => return Err(err!(WRONG_BOUND_MEMBER, range, &self.type_name(arg), &param_name, &name, &self.type_name(&expected), &self.type_name(found))),
+                        _ => {}
+                    }
+                }
+            } else if !self.is_assignable(arg, &constraint) {
+                return Err(err!(MISMATCHED_TYPES, range, &self.type_name(&constraint), &self.type_name(arg)));
+            }
+        }
+        Ok(())
+    }
+
+    // The members of a type which can be required by a constraint, or None if the type doesn't have members
+    fn get_members(&self, reference: &SymbolRef) -> Option<HashMap<String, SymbolRef>> {
+        self.collect_members(reference, false)
+    }
+
+    // The members which can be accessed with a dot, private fields included
+    fn get_fields(&self, reference: &SymbolRef) -> Option<HashMap<String, SymbolRef>> {
+        self.collect_members(reference, true)
+    }
+
+    fn collect_members(&self, reference: &SymbolRef, private: bool) -> Option<HashMap<String, SymbolRef>> {
+        let reference = self.resolve_alias(reference.clone());
+        let mut members = match reference.get_kind(self) {
+            SymbolKind::Struct(props) => props.iter().filter(|(_, prop)| private || !prop.flags.contains(ASTModifiers::PRIVATE)).map(|(name, prop)| (name.clone(), prop.kind.clone())).collect(),
+            SymbolKind::Enum(_) => HashMap::new(),
+            SymbolKind::TypeParam(Some(constraint)) => return self.collect_members(constraint, private),
+            SymbolKind::TypeParam(None) => return Some(HashMap::new()),
+            _ => return None
+        };
+        // Implementations for other instances of the type, like `impl TotalLen for Vec<str>` for `Vec<i32>`, don't apply
+        for implementation in &reference.to_symbol(self).impls {
+            let applies = self.impls.iter().find(|checked| checked.id == implementation.id).is_none_or(|checked| checked.target.instance_id == reference.instance_id || self.is_blanket(&checked.target));
+            let implementation = match self.get_impl_type_args(implementation.id, &reference) {
+                Some(type_args) => implementation.to_symbol(self).instances.iter().find(|instance| instance.type_args == type_args).map_or_else(|| implementation.clone(), |instance| SymbolRef::new_instance(implementation.id, instance.id)),
+                None => implementation.clone()
+            };
+            if let (true, SymbolKind::Struct(props)) = (applies, implementation.get_kind(self)) {
+                for (name, prop) in props {
+                    members.entry(name.clone()).or_insert_with(|| prop.kind.clone());
+                }
+            }
+        }
+        Some(members)
+    }
+
+    fn fill_instance(&mut self, id: u32, instance_id: usize) -> LazyResult<()> {
+        let sym = &self.symbols[&id];
+        let map = sym.type_params.iter().map(|param| param.id).zip(sym.instances[instance_id].type_args.iter().cloned()).collect::<Vec<(u32, SymbolRef)>>();
+        let kind = sym.kind.clone();
+        let kind = self.substitute_kind(&kind, &map)?.unwrap_or(kind);
+        self.symbols.get_mut(&id).unwrap().instances[instance_id].kind = kind;
+        Ok(())
+    }
+
+    // Replaces the type parameters in the type with the type arguments they map to
+    fn substitute(&mut self, reference: &SymbolRef, map: &[(u32, SymbolRef)]) -> LazyResult<SymbolRef> {
+        let substituted = if reference.is_type_param() {
+            match map.iter().find(|(id, _)| *id == reference.id) {
+                Some((_, arg)) => arg.clone(),
+                None => return Ok(reference.clone())
+            }
+        } else if let Some(instance_id) = reference.instance_id {
+            let type_args = reference.to_symbol(self).instances[instance_id].type_args.clone();
+            let substituted_args = type_args.iter().map(|arg| self.substitute(arg, map)).collect::<LazyResult<Vec<SymbolRef>>>()?;
+            if substituted_args == type_args { return Ok(reference.clone()) };
+            self.instantiate(reference.id, substituted_args, Range::default())?
+        } else {
+            let sym = reference.to_symbol(self);
+            // Declarations can't use the type parameters of another declaration, only types without a name can
+            if !matches!(sym.declaration, StatementOrExpression::None) { return Ok(reference.clone()) };
+            let kind = sym.kind.clone();
+            match self.substitute_kind(&kind, map)? {
+                Some(kind) => self.create_symbol(self.describe(&kind), kind),
+                None => return Ok(reference.clone())
+            }
+        };
+        Ok(if reference.is_optional() { substituted.make_optional() } else { substituted })
+    }
+
+    // Returns None if the kind doesn't contain any of the type parameters
+    fn substitute_kind(&mut self, kind: &SymbolKind, map: &[(u32, SymbolRef)]) -> LazyResult<Option<SymbolKind>> {
+        let mut changed = false;
+        let mut substitute = |checker: &mut Self, reference: &SymbolRef| -> LazyResult<SymbolRef> {
+            let substituted = checker.substitute(reference, map)?;
+            if substituted != *reference { changed = true };
+            Ok(substituted)
+        };
+        let kind = match kind {
+            SymbolKind::Struct(props) => {
+                let mut substituted: HashMap<String, SymbolProperty> = HashMap::new();
+                for (name, prop) in props {
+                    substituted.insert(name.clone(), SymbolProperty { kind: substitute(self, &prop.kind)?, flags: prop.flags });
+                }
+                SymbolKind::Struct(substituted)
+            },
+            SymbolKind::Enum(variants) => {
+                let mut substituted: HashMap<String, SymbolRef> = HashMap::new();
+                for (name, payload) in variants {
+                    substituted.insert(name.clone(), substitute(self, payload)?);
+                }
+                SymbolKind::Enum(substituted)
+            },
+            SymbolKind::Fn { parameters, return_type } => {
+                let mut substituted: Vec<(String, SymbolRef)> = Vec::new();
+                for (name, param) in parameters {
+                    substituted.push((name.clone(), substitute(self, param)?));
+                }
+                SymbolKind::Fn { parameters: substituted, return_type: substitute(self, return_type)? }
+            },
+            SymbolKind::Tuple(types) => SymbolKind::Tuple(types.iter().map(|typing| substitute(self, typing)).collect::<LazyResult<Vec<SymbolRef>>>()?),
+            SymbolKind::Alias(target) => SymbolKind::Alias(substitute(self, target)?),
+            _ => return Ok(None)
+        };
+        Ok(if changed { Some(kind) } else { None })
+    }
+
+    // The name of a type without a name, like `[i32, str]`
+    fn describe(&self, kind: &SymbolKind) -> String {
+        match kind {
+            SymbolKind::Tuple(types) => format!("[{}]", types.iter().map(|typing| self.type_name(typing)).collect::<Vec<String>>().join(", ")),
+            SymbolKind::Struct(props) => {
+                let mut props = props.iter().map(|(name, prop)| format!("{}: {}", name, self.type_name(&prop.kind))).collect::<Vec<String>>();
+                props.sort();
+                format!("{{{}}}", props.join(", "))
+            },
+            SymbolKind::Fn { parameters, return_type } => format!("({}) -> {}", parameters.iter().map(|(name, param)| format!("{}: {}", name, self.type_name(param))).collect::<Vec<String>>().join(", "), self.type_name(return_type)),
+            _ => String::new()
+        }
+    }
+
+    fn declare_type_params(&mut self, module: &mut Module, owner: u32, params: &[ASTTypeParameter]) -> LazyResult<()> {
+        for (ind, param) in params.iter().enumerate() {
+            if let Some(first) = params[..ind].iter().find(|other| other.name.value == param.name.value) {
+                return Err(err!(DUPLICATE_IDENT, param.name.range, &param.name.value).with_label("First defined here", first.name.range));
+            }
+            let reference = self.create_symbol(param.name.value.clone(), SymbolKind::TypeParam(None)).make_type_param();
+            self.type_params.insert(param.name.value.clone(), reference.clone());
+            self.symbols.get_mut(&owner).unwrap().type_params.push(reference);
+        }
+        // Constraints can refer to any of the type parameters
+        for param in params {
+            if let Some(constraint) = &param.constraint {
+                let constraint = self.get_sym_from_type(module, constraint)?;
+                let id = self.type_params[&param.name.value].id;
+                self.symbols.get_mut(&id).unwrap().kind = SymbolKind::TypeParam(Some(constraint));
+            }
+        }
         Ok(())
     }
 
-    fn check_struct(&mut self, module: &mut Module, structure: &ASTStruct) -> LazyResult<SymbolKind> {
-        let mut props: HashMap<String, SymbolRef> =  HashMap::new();
-        for prop in &structure.fields.pairs {
-            let sym_id = self.get_sym_from_type(module, prop.value.as_ref().unwrap(), structure.name.value == prop.name)?.to_symbol(self);
-            // TODO: If the property has a default value, get the type from it. The property's not guaranteed to have a type.
-            props.insert(prop.name.clone(), sym_id.to_ref());
+    fn check_fields(&mut self, module: &mut Module, fields: &ASTPairListTyping) -> LazyResult<HashMap<String, SymbolProperty>> {
+        let mut props: HashMap<String, SymbolProperty> = HashMap::new();
+        for (ind, field) in fields.pairs.iter().enumerate() {
+            if let Some(first) = fields.pairs[..ind].iter().find(|other| other.name == field.name) {
+                return Err(err!(DUPLICATE_FIELD, field.name_range, &field.name).with_label("First defined here", first.name_range));
+            }
+            let kind = self.get_sym_from_pair(module, field)?;
+            props.insert(field.name.clone(), SymbolProperty { kind, flags: field.modifiers });
+        }
+        Ok(props)
+    }
+
+    // The type of a field or a parameter comes from the annotation, or from the default value if there's no annotation
+    fn get_sym_from_pair(&mut self, module: &mut Module, pair: &ASTPairTypingItem) -> LazyResult<SymbolRef> {
+        if let Some(typing) = &pair.value {
+            let typing = self.get_sym_from_type(module, typing)?;
+            if let Some(default_value) = &pair.default_value {
+                self.check_exp_assignable(module, default_value, &typing)?;
+            }
+            return Ok(typing);
+        }
+        let default_value = if let Some(value) = &pair.default_value { value } else {
+            return Err(err!(CANNOT_INFER, pair.name_range, &pair.name));
+        };
+        match self.get_sym_from_exp(module, default_value)? {
+            Some(kind) => Ok(kind),
+            None => Err(err!(CANNOT_INFER, full_expression_range(default_value), &pair.name))
         }
-        panic!("TODO")
     }
 
     //
     // Checks if a type is valid.
-    // 
-    fn get_sym_from_type(&mut self, module: &mut Module, typing: &ASTTypings, handle_temps: bool) -> LazyResult<SymbolRef> {
+    //
+    fn get_sym_from_type(&mut self, module: &mut Module, typing: &ASTTypings) -> LazyResult<SymbolRef> {
         match typing {
-            ASTTypings::Var(name) => {
-                let sym_id = self.get_sym_from_var(module, &name.value, handle_temps)?;
-                let sym = self.symbols.get(&sym_id).unwrap();
-                if let Some(generics) = &name.typings {
-                    let checked_list = self.check_list(module, generics, handle_temps)?;
-                    let sym = self.symbols.get_mut(&sym_id).unwrap();
-                    Ok(sym.create_or_get_instance(checked_list, generics)?)
+            ASTTypings::Var(name) => self.get_sym_from_var_typing(module, name),
+            ASTTypings::Optional(typ) => {
+                let typing = self.get_sym_from_type(module, typ)?;
+                Ok(typing.make_optional())
+            }
+            ASTTypings::Mod(name) => self.get_sym_from_mod_access(module, name),
+            ASTTypings::Tuple(list) => {
+                let types = self.check_list(module, list)?;
+                Ok(self.create_symbol(format!("[{}]", list), SymbolKind::Tuple(types)))
+            },
+            ASTTypings::PairList(list) => {
+                let props = self.check_fields(module, list)?;
+                Ok(self.create_symbol(format!("{{{}}}", list), SymbolKind::Struct(props)))
+            },
+            ASTTypings::Function(func) => self.check_function(module, func, false, None).map(|func| func.unwrap()),
+            ASTTypings::Combine(combine) => self.check_combine(module, combine),
+            // `impl X` accepts any type with the members of the partial, whether they come from the type or from its implementations
+            ASTTypings::Impl(implementation) => {
+                let partial = self.get_sym_from_type(module, &implementation.value)?;
+                let members = self.get_members(&partial).ok_or_else(|| err!(NOT_A_PARTIAL, implementation.range, &implementation.value.to_string()))?;
+                let props = members.into_iter().map(|(name, kind)| (name, SymbolProperty { kind, flags: ASTModifiers::empty() })).collect();
+                Ok(self.create_symbol(typing.to_string(), SymbolKind::Struct(props)))
+            }
+        }
+    }
+
+    // The combined type has the members of both types. A member which is in both types gets the more specific type of the two.
+    fn check_combine(&mut self, module: &mut Module, combine: &ASTCombineTyping) -> LazyResult<SymbolRef> {
+        let mut props: HashMap<String, SymbolProperty> = HashMap::new();
+        for side in [&combine.left, &combine.right] {
+            let typing = self.get_sym_from_type(module, side)?;
+            let members = self.get_members(&typing).ok_or_else(|| err!(NOT_COMBINABLE, combine.range, &self.type_name(&typing)))?;
+            for (name, kind) in members {
+                match props.get(&name) {
+                    Some(existing) if self.is_assignable(&existing.kind, &kind) => {},
+                    Some(existing) if !self.is_assignable(&kind, &existing.kind) => return Err(err!(WRONG_FIELD_TYPE, combine.range, &self.type_name(&typing), &combine.left.to_string(), &name)),
+                    _ => { props.insert(name, SymbolProperty { kind, flags: ASTModifiers::empty() }); }
                 }
-                else {
-                    Ok(SymbolRef::new_ref(sym.id))
+            }
+        }
+        let kind = SymbolKind::Struct(props);
+        Ok(self.create_symbol(self.describe(&kind), kind))
+    }
+
+    fn get_sym_from_var_typing(&mut self, module: &mut Module, name: &ASTVarTyping) -> LazyResult<SymbolRef> {
+        let reference = self.get_sym_from_var(module, &name.value)?;
+        if let Some(generics) = &name.typings {
+            let checked_list = self.check_list(module, generics)?;
+            let instance = self.instantiate(reference.id, checked_list.clone(), generics.range)?;
+            self.check_bounds(reference.id, &checked_list, generics.range)?;
+            Ok(self.resolve_alias(instance))
+        }
+        else {
+            Ok(reference)
+        }
+    }
+
+    fn get_sym_from_mod_access_values(&mut self, module: &mut Module, values: &ASTModAccessValues) -> LazyResult<SymbolRef> {
+        match values {
+            ASTModAccessValues::Var(var) => self.get_sym_from_var_typing(module, var),
+            ASTModAccessValues::ModAccess(access) => self.get_sym_from_mod_access(module, access)
+        }
+    }
+
+    fn get_sym_from_mod_access(&mut self, module: &mut Module, name: &ASTModAccess) -> LazyResult<SymbolRef> {
+        let mut val = self.get_sym_from_var(module, &name.path[0])?.to_symbol(self);
+        let mut is_enum = false;
+        for var in &name.path[1..] {
+            if let Some(typ) = val.get_mod_type(self, &var.value) {
+                if val.kind.is_enum() { is_enum = true };
+                val = typ.to_symbol(self);
+            } else {
+                return Err(err!(NAME_NOT_FOUND, var.range, &var.value))
+            }
+        };
+        if is_enum {
+            Err(err!(VAL_AS_TYPE, name.range))
+        } else {
+            Ok(val.to_ref())
+        }
+    }
+
+    // The type of a function. Functions without a return type return `none`, unless they're expressions, in which
+    // case the return type comes from the expected function type or from the body. Bodies are only checked inside
+    // other bodies, so the return type of a function in a declaration cannot be inferred from its body.
+    fn check_function(&mut self, module: &mut Module, func: &ASTFunction, is_expression: bool, expected: Option<&SymbolRef>) -> LazyResult<Option<SymbolRef>> {
+        let expected = match expected.map(|expected| self.resolve_alias(expected.clone())).as_ref().map(|expected| expected.get_kind(self)) {
+            Some(SymbolKind::Fn { parameters, return_type }) => Some((parameters.clone(), return_type.clone())),
+            _ => None
+        };
+        if is_expression && func.return_type.is_none() && func.body.is_some() && expected.is_none() && self.values.is_empty() {
+            return Ok(None);
+        }
+        let reference = self.create_symbol(String::new(), SymbolKind::None);
+        let outer_type_params = self.type_params.clone();
+        let kind = self.check_function_kind(module, reference.id, func, is_expression, expected.as_ref());
+        self.type_params = outer_type_params;
+        let kind = if let Some(kind) = kind? { kind } else { return Ok(None) };
+        let name = self.describe(&kind);
+        let sym = self.symbols.get_mut(&reference.id).unwrap();
+        sym.name = name;
+        sym.kind = kind;
+        Ok(Some(reference))
+    }
+
+    fn check_function_kind(&mut self, module: &mut Module, id: u32, func: &ASTFunction, is_expression: bool, expected: Option<&(Vec<(String, SymbolRef)>, SymbolRef)>) -> LazyResult<Option<SymbolKind>> {
+        self.declare_type_params(module, id, &func.typings)?;
+        let mut parameters: Vec<(String, SymbolRef)> = Vec::new();
+        for (ind, param) in func.params.pairs.iter().enumerate() {
+            // Parameters without a type get it from the expected function type
+            let typing = match expected.and_then(|(expected_params, _)| expected_params.get(ind)) {
+                Some((_, typing)) if param.value.is_none() && param.default_value.is_none() => typing.clone(),
+                _ => self.get_sym_from_pair(module, param)?
+            };
+            parameters.push((param.name.clone(), typing));
+        }
+        let return_type = match (&func.return_type, expected) {
+            (Some(typing), _) => Some(self.get_sym_from_type(module, typing)?),
+            (None, Some((_, return_type))) => Some(return_type.clone()),
+            (None, None) if func.body.is_none() || !is_expression => Some(self.get_primitive("none")),
+            (None, None) => None
+        };
+        let return_type = match &func.body {
+            Some(body) if !self.values.is_empty() => {
+                let scope = parameters.iter().cloned().collect::<HashMap<String, SymbolRef>>();
+                let found = self.in_scope(scope, |checker| checker.check_branch(module, body, return_type.as_ref()))?;
+                return_type.or(found)
+            },
+            _ => return_type
+        };
+        // Only calls care about which parameters can be left out, inside the body they're plain values
+        let parameters = parameters.into_iter().zip(func.params.pairs.iter()).map(|((name, typing), param)| {
+            (name, if param.spread { typing.make_spread() } else if param.default_value.is_some() { typing.make_default() } else { typing })
+        }).collect();
+        Ok(return_type.map(|return_type| SymbolKind::Fn { parameters, return_type }))
+    }
+
+    // The type of an expression, or None if it cannot be inferred without more context
+    fn get_sym_from_exp(&mut self, module: &mut Module, exp: &ASTExpression) -> LazyResult<Option<SymbolRef>> {
+        Ok(match exp {
+            ASTExpression::Int(_) => Some(self.get_primitive("i32")),
+            ASTExpression::Float(_) => Some(self.get_primitive("f32")),
+            ASTExpression::Str(_) => Some(self.get_primitive("str")),
+            ASTExpression::TempStr(temp) => {
+                self.check_template(module, temp)?;
+                Some(self.get_primitive("str"))
+            },
+            ASTExpression::Char(_) => Some(self.get_primitive("char")),
+            ASTExpression::Bool(_) => Some(self.get_primitive("bool")),
+            ASTExpression::Var(var) => Some(self.get_value(module, var)?),
+            ASTExpression::Binary(binary) if binary.op == "=" => self.check_operands(module, &binary.op, &binary.left, &binary.right, full_expression_range(exp))?,
+            ASTExpression::Unary(_) | ASTExpression::Binary(_) | ASTExpression::DotAccess(_) | ASTExpression::Call(_) | ASTExpression::Optional(_) | ASTExpression::IndexAccess(_) => self.check_chain(module, exp)?,
+            ASTExpression::Block(block) => self.check_block(module, block, None)?,
+            ASTExpression::If(if_exp) => self.check_if(module, if_exp, None)?,
+            ASTExpression::Match(match_exp) => self.check_match(module, match_exp, None)?,
+            ASTExpression::Declare(decl) => {
+                self.check_declare(module, decl)?;
+                Some(self.get_primitive("none"))
+            },
+            ASTExpression::ForIn(for_in) => {
+                self.check_for_in(module, for_in)?;
+                Some(self.get_primitive("none"))
+            },
+            ASTExpression::While(while_loop) => {
+                self.check_exp_assignable(module, &while_loop.condition, &self.get_primitive("bool"))?;
+                self.check_statement(module, &while_loop.body)?;
+                Some(self.get_primitive("none"))
+            },
+            // Ranges don't have a type yet, they can only be iterated over
+            ASTExpression::Iterator(iter) => {
+                self.check_operands(module, if iter.inclusive { "..=" } else { ".." }, &iter.start, &iter.end, iter.range)?;
+                None
+            },
+            ASTExpression::Tuple(list) => {
+                let mut types: Vec<SymbolRef> = Vec::new();
+                for element in &list.expressions {
+                    if let Some(typing) = self.get_sym_from_exp(module, element)? { types.push(typing) } else { return Ok(None) };
                 }
+                let kind = SymbolKind::Tuple(types);
+                Some(self.create_symbol(self.describe(&kind), kind))
             },
-            ASTTypings::Optional(typ) => {
-                let typing = self.get_sym_from_type(module, typ, handle_temps)?;
-                Ok(typing.clone().make_optional())
-            }
-            ASTTypings::Mod(name) => {
-                let mut val = self.get_sym_from_var(module, &name.path[0], handle_temps)?.to_symbol(self);
-                let mut is_enum = false;
-                for i in 1..name.path.len() {
-                    let var = &name.path[i];
-                    if let Some(typ) = val.get_mod_type(self, &var.value) {
-                        if val.kind.is_enum() { is_enum = true };
-                        val = typ.to_symbol(self);
-                    } else {
-                        return Err(err!(NAME_NOT_FOUND, var.range, &var.value))
-                    }
+            ASTExpression::None(_) => Some(self.get_primitive("none")),
+            ASTExpression::Init(init) => Some(self.check_init(module, init)?),
+            ASTExpression::Function(func) => self.check_function(module, func, true, None)?,
+            ASTExpression::ModAccess(access) => self.check_mod_access_exp(module, access, None)?,
+            ASTExpression::Spread(spread) => {
+                self.get_sym_from_exp(module, &spread.value)?;
+                None
+            },
+            ASTExpression::Await(await_exp) => {
+                self.get_sym_from_exp(module, &await_exp.expression)?;
+                None
+            },
+            ASTExpression::Yield(yield_exp) => {
+                if let Some(value) = &yield_exp.value { self.get_sym_from_exp(module, value)?; }
+                None
+            }
+        })
+    }
+
+    // Chains like `a.b().c()` and `1 + 2 + 3` lean left, so they're checked from the innermost value outwards with a loop
+    // instead of recursing once per link
+    fn check_chain(&mut self, module: &mut Module, exp: &ASTExpression) -> LazyResult<Option<SymbolRef>> {
+        let mut links: Vec<&ASTExpression> = Vec::new();
+        let mut current = exp;
+        loop {
+            let next = match current {
+                ASTExpression::Binary(binary) if binary.op != "=" => &binary.left,
+                ASTExpression::Unary(unary) => &unary.value,
+                ASTExpression::DotAccess(access) => &access.value,
+                ASTExpression::Call(call) => &call.target,
+                ASTExpression::Optional(optional) => &optional.value,
+                ASTExpression::IndexAccess(access) => &access.value,
+                _ => break
+            };
+            links.push(current);
+            current = next;
+        }
+        let mut value = self.get_sym_from_exp(module, current)?;
+        for link in links.into_iter().rev() {
+            value = match link {
+                ASTExpression::Binary(binary) => {
+                    let right = self.get_sym_from_exp(module, &binary.right)?;
+                    self.check_operand_types(&binary.op, &binary.left, &binary.right, value, right, full_expression_range(link))?
+                },
+                ASTExpression::Unary(unary) => self.check_unary(unary, value, full_expression_range(link))?,
+                ASTExpression::DotAccess(access) => self.check_dot_access(access, value)?,
+                ASTExpression::Call(call) => self.check_call(module, call, value)?,
+                // `value?` is the value without `none`
+                ASTExpression::Optional(_) => value.map(|value| value.make_required()),
+                ASTExpression::IndexAccess(access) => {
+                    self.get_sym_from_exp(module, &access.target)?;
+                    None
+                },
+                _ => unreachable!()
+            };
+        }
+        Ok(value)
+    }
+
+    // Expressions whose value isn't used. The branches of ifs and matches which are statements can have different types.
+    fn check_statement(&mut self, module: &mut Module, exp: &ASTExpression) -> LazyResult<()> {
+        let none = self.get_primitive("none");
+        match exp {
+            ASTExpression::If(if_exp) => self.check_if(module, if_exp, Some(&none))?,
+            ASTExpression::Match(match_exp) => self.check_match(module, match_exp, Some(&none))?,
+            ASTExpression::Block(block) => self.check_block(module, block, Some(&none))?,
+            _ => self.get_sym_from_exp(module, exp)?
+        };
+        Ok(())
+    }
+
+    // Checks a branch of a block, an if or a match against the type the whole expression is expected to have.
+    // Branches of expressions which are expected to be `none` are statements.
+    fn check_branch(&mut self, module: &mut Module, exp: &ASTExpression, expected: Option<&SymbolRef>) -> LazyResult<Option<SymbolRef>> {
+        match expected {
+            Some(expected) if self.is_none(expected) => self.check_statement(module, exp)?,
+            Some(expected) => self.check_exp_assignable(module, exp, expected)?,
+            None => return self.get_sym_from_exp(module, exp)
+        };
+        Ok(expected.cloned())
+    }
+
+    fn is_none(&self, reference: &SymbolRef) -> bool {
+        reference.id == self.primitives["none"] && !reference.is_optional()
+    }
+
+    // The type which the types of both branches can be assigned to
+    fn join_types(&self, first: Option<SymbolRef>, second: Option<SymbolRef>, range: Range) -> LazyResult<Option<SymbolRef>> {
+        let (first, second) = if let (Some(first), Some(second)) = (first, second) { (first, second) } else { return Ok(None) };
+        if self.is_assignable(&second, &first) {
+            Ok(Some(first))
+        } else if self.is_assignable(&first, &second) {
+            Ok(Some(second))
+        } else if self.is_none(&second) {
+            Ok(Some(first.make_optional()))
+        } else if self.is_none(&first) {
+            Ok(Some(second.make_optional()))
+        } else {
+            Err(err!(MISMATCHED_TYPES, range, &self.type_name(&first), &self.type_name(&second)))
+        }
+    }
+
+    // The value of a block is the value of its last expression
+    fn check_block(&mut self, module: &mut Module, block: &ASTBlock, expected: Option<&SymbolRef>) -> LazyResult<Option<SymbolRef>> {
+        self.in_scope(HashMap::new(), |checker| {
+            let (last, elements) = if let Some(split) = block.elements.split_last() { split } else {
+                let none = checker.get_primitive("none");
+                if let Some(expected) = expected { checker.check_assignable(&none, expected, block.range)? };
+                return Ok(Some(none));
+            };
+            for element in elements {
+                checker.check_statement(module, element)?;
+            }
+            checker.check_branch(module, last, expected)
+        })
+    }
+
+    // Without an else branch, the value of an if is optional
+    fn check_if(&mut self, module: &mut Module, if_exp: &ASTIf, expected: Option<&SymbolRef>) -> LazyResult<Option<SymbolRef>> {
+        self.check_exp_assignable(module, &if_exp.condition, &self.get_primitive("bool"))?;
+        match (expected, &if_exp.otherwise) {
+            (Some(expected), Some(otherwise)) => {
+                self.check_branch(module, &if_exp.then, Some(expected))?;
+                self.check_branch(module, otherwise, Some(expected))
+            },
+            (Some(expected), None) => {
+                self.check_assignable(&self.get_primitive("none"), expected, if_exp.range)?;
+                self.check_branch(module, &if_exp.then, Some(expected))
+            },
+            (None, Some(otherwise)) => {
+                let then = self.get_sym_from_exp(module, &if_exp.then)?;
+                let otherwise_type = self.get_sym_from_exp(module, otherwise)?;
+                self.join_types(then, otherwise_type, full_expression_range(otherwise))
+            },
+            (None, None) => Ok(self.get_sym_from_exp(module, &if_exp.then)?.map(|then| then.make_optional()))
+        }
+    }
+
+    // The arms are checked against the type of the matched value, and their variables are declared in the body of the arm
+    fn check_match(&mut self, module: &mut Module, match_exp: &ASTMatch, expected: Option<&SymbolRef>) -> LazyResult<Option<SymbolRef>> {
+        let value = self.get_sym_from_exp(module, &match_exp.expression)?;
+        let mut result = None;
+        for (ind, arm) in match_exp.arms.iter().enumerate() {
+            let mut scope: HashMap<String, SymbolRef> = HashMap::new();
+            for possibility in &arm.possibilities {
+                self.check_arm_pattern(module, possibility, value.as_ref(), &mut scope)?;
+            }
+            let body = self.in_scope(scope, |checker| {
+                if let Some(guard) = &arm.guard { checker.check_exp_assignable(module, guard, &checker.get_primitive("bool"))? };
+                checker.check_branch(module, &arm.body, expected)
+            })?;
+            result = if ind == 0 || expected.is_some() { body } else { self.join_types(result, body, full_expression_range(&arm.body))? };
+        }
+        Ok(if match_exp.arms.is_empty() { Some(self.get_primitive("none")) } else { result })
+    }
+
+    fn check_arm_pattern(&mut self, module: &mut Module, possibility: &ASTMatchArmExpressions, value: Option<&SymbolRef>, scope: &mut HashMap<String, SymbolRef>) -> LazyResult<()> {
+        let (found, range) = match possibility {
+            ASTMatchArmExpressions::String(string) => (self.get_primitive("str"), string.range),
+            ASTMatchArmExpressions::Int(int) if !value.is_some_and(|value| self.is_number(value)) => (self.get_primitive("i32"), int.range),
+            ASTMatchArmExpressions::Float(float) => (self.get_primitive("f32"), float.range),
+            ASTMatchArmExpressions::Char(character) => (self.get_primitive("char"), character.range),
+            ASTMatchArmExpressions::Bool(boolean) => (self.get_primitive("bool"), boolean.range),
+            ASTMatchArmExpressions::None(range) => (self.get_primitive("none"), *range),
+            ASTMatchArmExpressions::Iterator(iter) => {
+                if let Some(value) = value {
+                    self.check_exp_assignable(module, &iter.start, value)?;
+                    self.check_exp_assignable(module, &iter.end, value)?;
+                }
+                return Ok(());
+            },
+            ASTMatchArmExpressions::Tuple(list) => {
+                let types = match value.map(|value| self.resolve_alias(value.clone())).as_ref().map(|value| value.get_kind(self)) {
+                    Some(SymbolKind::Tuple(types)) if types.len() == list.expressions.len() => types.clone(),
+                    Some(_) => return Err(err!(MISMATCHED_TYPES, list.range, &self.type_name(value.unwrap()), &format!("a tuple with {} elements", list.expressions.len()))),
+                    None => return Ok(())
                 };
-                if is_enum {
-                    Err(err!(VAL_AS_TYPE, name.range))
-                } else {
-                    Ok(val.to_ref())
+                for (element, typing) in list.expressions.iter().zip(types) {
+                    if !matches!(element, ASTExpression::Var(var) if var.value == "_") { self.check_exp_assignable(module, element, &typing)? };
+                }
+                return Ok(());
+            },
+            ASTMatchArmExpressions::Enum(access) | ASTMatchArmExpressions::EnumVar(access) => return self.check_variant_pattern(module, access, value, scope),
+            ASTMatchArmExpressions::Int(_) | ASTMatchArmExpressions::Rest => return Ok(())
+        };
+        match value {
+            Some(value) => self.check_assignable(&found, value, range),
+            None => Ok(())
+        }
+    }
+
+    // `Enum::Variant(name)` declares the variable with the value of the variant, with the type arguments of the matched value
+    fn check_variant_pattern(&mut self, module: &mut Module, access: &ASTModAccess, value: Option<&SymbolRef>, scope: &mut HashMap<String, SymbolRef>) -> LazyResult<()> {
+        let (variant, path) = access.path.split_last().unwrap();
+        let mut enumeration = self.get_sym_from_var(module, &path[0])?;
+        for var in &path[1..] {
+            enumeration = enumeration.to_symbol(self).get_mod_type(self, &var.value).cloned().ok_or_else(|| err!(NAME_NOT_FOUND, var.range, &var.value))?;
+        }
+        let payload = match enumeration.get_kind(self) {
+            SymbolKind::Enum(variants) => variants.get(&variant.value).cloned(),
+            _ => None
+        }.ok_or_else(|| err!(NAME_NOT_FOUND, variant.range, &variant.value))?;
+        let payload = match value.map(|value| self.resolve_alias(value.clone())) {
+            Some(value) if value.id == enumeration.id => {
+                let type_args = value.instance_id.map_or_else(Vec::new, |id| value.to_symbol(self).instances[id].type_args.clone());
+                let map = enumeration.to_symbol(self).type_params.iter().map(|param| param.id).zip(type_args).collect::<Vec<(u32, SymbolRef)>>();
+                self.substitute(&payload, &map)?
+            },
+            Some(value) => return Err(err!(MISMATCHED_TYPES, access.range, &self.type_name(&value), &self.type_name(&enumeration))),
+            None => payload
+        };
+        match access.init.as_ref().map(|init| init.expressions.as_slice()) {
+            Some([ASTExpression::Var(var)]) => {
+                scope.insert(var.value.clone(), payload);
+            },
+            Some(values) => {
+                for value in values {
+                    self.check_exp_assignable(module, value, &payload)?;
+                }
+            },
+            None => {}
+        }
+        Ok(())
+    }
+
+    fn check_declare(&mut self, module: &mut Module, decl: &ASTDeclare) -> LazyResult<()> {
+        let typing = match &decl.typings {
+            Some(typing) => Some(self.get_sym_from_type(module, typing)?),
+            None => None
+        };
+        let range = match &decl.var {
+            ASTDeclareTypes::Var(var) => var.range,
+            ASTDeclareTypes::TupleDeconstruct(list) | ASTDeclareTypes::StructDeconstruct(list) => list.range
+        };
+        let typing = self.check_initializer(module, decl.value.as_deref(), typing, &decl.var.to_string(), range)?;
+        self.declare_pattern(module, &decl.var, typing)
+    }
+
+    // Declares the variables of a pattern in the innermost scope
+    fn declare_pattern(&mut self, module: &mut Module, pattern: &ASTDeclareTypes, typing: SymbolRef) -> LazyResult<()> {
+        match pattern {
+            ASTDeclareTypes::Var(var) => {
+                self.values.last_mut().unwrap().insert(var.value.clone(), typing);
+            },
+            ASTDeclareTypes::TupleDeconstruct(list) => {
+                let types = match self.resolve_alias(typing.clone()).get_kind(self) {
+                    SymbolKind::Tuple(types) if !typing.is_optional() => types.clone(),
+                    _ => return Err(err!(INVALID_DECONSTRUCT, list.range, &self.type_name(&typing)))
+                };
+                for (ind, item) in list.values.iter().enumerate() {
+                    // The rest element is a tuple of the remaining elements
+                    let element = if item.spread {
+                        let kind = SymbolKind::Tuple(types.get(ind..).unwrap_or_default().to_vec());
+                        self.create_symbol(self.describe(&kind), kind)
+                    } else if let Some(element) = types.get(ind) {
+                        element.clone()
+                    } else {
+                        return Err(err!(INVALID_DECONSTRUCT, item.range, &self.type_name(&typing); [format!("The tuple only has {} elements.", types.len())]));
+                    };
+                    self.declare_item(module, item, element)?;
+                }
+            },
+            ASTDeclareTypes::StructDeconstruct(list) => {
+                let members = self.get_fields(&typing).filter(|_| !typing.is_optional()).ok_or_else(|| err!(INVALID_DECONSTRUCT, list.range, &self.type_name(&typing)))?;
+                for item in &list.values {
+                    // The rest element is a struct with the fields which aren't deconstructed
+                    let member = if item.spread {
+                        let taken = list.values.iter().filter(|other| !other.spread).filter_map(deconstructed_field).map(|field| field.value.clone()).collect::<Vec<String>>();
+                        let kind = SymbolKind::Struct(members.iter().filter(|(name, _)| !taken.contains(name)).map(|(name, kind)| (name.clone(), SymbolProperty { kind: kind.clone(), flags: ASTModifiers::empty() })).collect());
+                        self.create_symbol(self.describe(&kind), kind)
+                    } else {
+                        let field = if let Some(field) = deconstructed_field(item) { field } else { continue };
+                        members.get(&field.value).cloned().ok_or_else(|| err!(NO_MEMBER, field.range, &field.value, &self.type_name(&typing)))?
+                    };
+                    self.declare_item(module, item, member)?;
                 }
             }
-            _ => Err(err!(UNEXPECTED_EOF, Range::default(), &module.filename))
         }
+        Ok(())
     }
 
-    fn get_sym_from_var(&mut self, module: &mut Module, var: &ASTVar, handle_temps: bool) -> LazyResult<u32> {
-        match module.temporary.remove(&var.value) {
-            Some(mut val) if handle_temps => {
-                val.kind = match &val.declaration {
-                    StatementOrExpression::StructStatement(structure) => self.check_struct(module, structure)?,
-                    _ => SymbolKind::None
+    // A default value replaces `none`, so the variable isn't optional
+    fn declare_item(&mut self, module: &mut Module, item: &ASTDeconstructItem, typing: SymbolRef) -> LazyResult<()> {
+        let typing = match &item.default_value {
+            Some(default_value) => {
+                let typing = typing.make_required();
+                self.check_exp_assignable(module, default_value, &typing)?;
+                typing
+            },
+            None => typing
+        };
+        self.declare_pattern(module, &item.pattern, typing)
+    }
+
+    // The type of the variable comes from the range, or from the `next` member of the iterated value
+    fn check_for_in(&mut self, module: &mut Module, for_in: &ASTForIn) -> LazyResult<()> {
+        let element = match &*for_in.iterable {
+            ASTExpression::Iterator(iter) => self.check_operands(module, if iter.inclusive { "..=" } else { ".." }, &iter.start, &iter.end, iter.range)?,
+            iterable => match self.get_sym_from_exp(module, iterable)?.and_then(|iterable| self.get_fields(&iterable)).and_then(|fields| fields.get("next").cloned()) {
+                Some(next) => match next.get_kind(self) {
+                    SymbolKind::Fn { return_type, .. } => Some(return_type.clone().make_required()),
+                    _ => None
+                },
+                None => None
+            }
+        };
+        let element = element.ok_or_else(|| err!(CANNOT_INFER, for_in.var.range, &for_in.var.value))?;
+        self.in_scope(HashMap::from([(for_in.var.value.clone(), element)]), |checker| checker.check_statement(module, &for_in.body))
+    }
+
+    fn check_unary(&self, unary: &ASTUnary, value: Option<SymbolRef>, range: Range) -> LazyResult<Option<SymbolRef>> {
+        let found = if let Some(found) = value { found } else { return Ok(None) };
+        let is_valid = !found.is_optional() && match unary.op.as_str() {
+            "!" => found.id == self.primitives["bool"],
+            "-" => self.is_number(&found),
+            "~" => self.is_integer(&found),
+            _ => true
+        };
+        if !is_valid {
+            return Err(err!(INVALID_OPERAND, range, &unary.op, &self.type_name(&found)));
+        }
+        Ok(Some(found))
+    }
+
+    // Assignments, arithmetic, comparisons and ranges. Integer literals get the type of the other operand.
+    fn check_operands(&mut self, module: &mut Module, op: &str, left: &ASTExpression, right: &ASTExpression, range: Range) -> LazyResult<Option<SymbolRef>> {
+        if op == "=" {
+            return match self.get_sym_from_exp(module, left)? {
+                Some(target) => {
+                    self.check_exp_assignable(module, right, &target)?;
+                    Ok(Some(target))
+                },
+                None => self.get_sym_from_exp(module, right)
+            };
+        }
+        let (left_type, right_type) = (self.get_sym_from_exp(module, left)?, self.get_sym_from_exp(module, right)?);
+        self.check_operand_types(op, left, right, left_type, right_type, range)
+    }
+
+    // The type of an operation on operands which are already checked
+    fn check_operand_types(&self, op: &str, left: &ASTExpression, right: &ASTExpression, left_type: Option<SymbolRef>, right_type: Option<SymbolRef>, range: Range) -> LazyResult<Option<SymbolRef>> {
+        let bool_type = self.get_primitive("bool");
+        let is_comparison = matches!(op, "<" | ">" | "<=" | ">=" | "==" | "!=" | "&&");
+        let (left_type, right_type) = match (left_type, right_type) {
+            (Some(left_type), Some(right_type)) => (left_type, right_type),
+            _ => return Ok(if is_comparison { Some(bool_type) } else { None })
+        };
+        let is_number = |checker: &Self, typing: &SymbolRef| !typing.is_optional() && checker.is_number(typing);
+        let left_type = if is_int_literal(left) && is_number(self, &right_type) { right_type.clone() } else { left_type };
+        let right_type = if is_int_literal(right) && is_number(self, &left_type) { left_type.clone() } else { right_type };
+        let same = left_type == right_type;
+        let result = match op.strip_suffix('=').filter(|op| matches!(*op, "+" | "-" | "*" | "/" | "%")).unwrap_or(op) {
+            "+" if same && (is_number(self, &left_type) || left_type == self.get_primitive("str")) => Some(left_type.clone()),
+            "-" | "*" | "/" | "%" | ".." | "..=" if same && is_number(self, &left_type) => Some(left_type.clone()),
+            "&" | "|" | "^" if same && (self.is_integer(&left_type) || left_type == bool_type) => Some(left_type.clone()),
+            "<<" | ">>" | ">>>" if self.is_integer(&left_type) && self.is_integer(&right_type) => Some(left_type.clone()),
+            "<" | ">" | "<=" | ">=" if same && (is_number(self, &left_type) || left_type == self.get_primitive("char")) => Some(bool_type),
+            "==" | "!=" if self.is_assignable(&left_type, &right_type) || self.is_assignable(&right_type, &left_type) => Some(bool_type),
+            "&&" | "||" if left_type == bool_type && right_type == bool_type => Some(bool_type),
+            // `value || default` replaces `none`
+            "||" if left_type.is_optional() && self.is_assignable(&right_type, &left_type.clone().make_required()) => Some(left_type.clone().make_required()),
+            _ => None
+        };
+        result.map(Some).ok_or_else(|| err!(INVALID_OPERANDS, range, op, &self.type_name(&left_type), &self.type_name(&right_type)))
+    }
+
+    fn is_integer(&self, reference: &SymbolRef) -> bool {
+        !reference.is_optional() && self.is_number(reference) && reference.id != self.primitives["f32"]
+    }
+
+    // Accessing a member of an optional value gives an optional value
+    fn check_dot_access(&self, access: &ASTDotAccess, value: Option<SymbolRef>) -> LazyResult<Option<SymbolRef>> {
+        let value = if let Some(value) = value { value } else { return Ok(None) };
+        let member = self.get_fields(&value).and_then(|fields| fields.get(&access.target.value).cloned()).ok_or_else(|| err!(NO_MEMBER, access.target.range, &access.target.value, &self.type_name(&value)))?;
+        Ok(Some(if value.is_optional() { member.make_optional() } else { member }))
+    }
+
+    fn check_template(&mut self, module: &mut Module, temp: &ASTTempStr) -> LazyResult<()> {
+        for part in &temp.parts {
+            if let ASTTempStrPart::Interpolation(interpolation) = part {
+                let value = self.get_sym_from_exp(module, &interpolation.value)?;
+                if let (Some(value), Some(spec)) = (value, &interpolation.format) {
+                    self.check_format_spec(spec, &value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // `?` formats any value. Every other format option only works with some types.
+    fn check_format_spec(&self, spec: &ASTFormatSpec, value: &SymbolRef) -> LazyResult<()> {
+        let is_number = !value.is_optional() && self.is_number(value);
+        let note = match spec.kind {
+            Some('?') => None,
+            Some('x' | 'X' | 'b' | 'o') if !self.is_integer(value) => Some("Hexadecimal, binary and octal formats can only be used with integers."),
+            Some('e' | 'E') if !is_number => Some("Scientific notation can only be used with numbers."),
+            _ if (spec.sign || spec.zero_pad) && !is_number => Some("Signs and zero padding can only be used with numbers."),
+            _ if spec.precision.is_some() && !is_number && *value != self.get_primitive("str") => Some("A precision can only be used with numbers and strings."),
+            None if spec.alternate => Some("The alternate form can only be used with a format kind, like `#x`."),
+            _ => None
+        };
+        match note {
+            Some(note) => Err(err!(FORMAT_SPEC_MISMATCH, spec.range, &spec.to_string(), &self.type_name(value); [note])),
+            None => Ok(())
+        }
+    }
+
+    // The type of a variable, a parameter or a static
+    fn get_value(&mut self, module: &mut Module, var: &ASTVar) -> LazyResult<SymbolRef> {
+        if let Some(typing) = self.values.iter().rev().find_map(|scope| scope.get(&var.value)) {
+            return Ok(typing.clone());
+        }
+        let reference = if module.temporary.contains_key(&var.value) {
+            self.check_declaration(module, &var.value)?
+        } else if let Some(reference) = module.get_sym(&var.value) {
+            reference.clone()
+        } else if self.type_params.contains_key(&var.value) || self.primitives.contains_key(&var.value) {
+            return Err(err!(TYPE_AS_VALUE, var.range, &var.value));
+        } else {
+            return Err(err!(NAME_NOT_FOUND, var.range, &var.value));
+        };
+        match reference.get_kind(self) {
+            SymbolKind::Static(typing) => Ok(typing.clone()),
+            _ if self.static_stack.contains(&reference.id) => Err(err!(CANNOT_INFER, var.range, &var.value)),
+            _ => Err(err!(TYPE_AS_VALUE, var.range, &var.value))
+        }
+    }
+
+    // The type of the call is the return type of the function, with the type arguments of the call. The type
+    // arguments of generic functions are inferred from the arguments, if they're not given.
+    fn check_call(&mut self, module: &mut Module, call: &ASTCall, target: Option<SymbolRef>) -> LazyResult<Option<SymbolRef>> {
+        let (parameters, return_type, target) = match target {
+            Some(target) => match target.get_kind(self) {
+                SymbolKind::Fn { parameters, return_type } => (parameters.clone(), return_type.clone(), target),
+                _ => return Err(err!(INVALID_OPERAND, full_expression_range(&call.target), "()", &self.type_name(&target)))
+            },
+            None => {
+                for arg in &call.args.expressions {
+                    self.get_sym_from_exp(module, arg)?;
+                }
+                return Ok(None);
+            }
+        };
+        let type_params = self.symbols[&target.id].type_params.clone();
+        // The arguments which the type arguments are inferred from aren't checked again, their parameter is their own type
+        let mut inferred_from: Vec<usize> = Vec::new();
+        let (type_args, range) = if let Some(typings) = &call.typings {
+            let type_args = self.check_list(module, typings)?;
+            if type_args.len() != type_params.len() {
+                return Err(err!(INVALID_AMOUNT_OF_TYPE_PARAMS, typings.range, &type_params.len().to_string(), &type_args.len().to_string()));
+            }
+            (type_args, typings.range)
+        } else {
+            let mut type_args: Vec<SymbolRef> = Vec::new();
+            for param in &type_params {
+                let position = parameters.iter().position(|(_, typing)| typing.id == param.id && !typing.is_optional());
+                let arg = match position.and_then(|ind| Some((ind, call.args.expressions.get(ind)?))) {
+                    Some((ind, arg)) => {
+                        inferred_from.push(ind);
+                        self.get_sym_from_exp(module, arg)?
+                    },
+                    None => None
                 };
-                let sym_id = val.id;
-                self.symbols.insert(val.id, val);
-                Ok(sym_id)
+                if let Some(arg) = arg { type_args.push(arg) } else { break };
+            }
+            (type_args, full_expression_range(&call.target).end_with(&call.range.end))
+        };
+        // The type arguments which couldn't be inferred accept any argument
+        let is_inferred = type_args.len() == type_params.len();
+        if is_inferred { self.check_bounds(target.id, &type_args, range)? };
+        // A spread parameter takes all of the remaining arguments, and parameters which are optional or have a default value can be left out
+        let args = &call.args.expressions;
+        let is_variadic = parameters.last().is_some_and(|(_, param)| param.is_spread());
+        if args.len() > parameters.len() && !is_variadic {
+            let extra = &args[parameters.len()..];
+            let range = full_expression_range(&extra[0]).end_with(&full_expression_range(&extra[extra.len() - 1]).end);
+            return Err(err!(TOO_MANY_ARGS, range, &parameters.len().to_string(), &args.len().to_string()));
+        }
+        if let Some((name, _)) = parameters.iter().skip(args.len()).find(|(_, param)| !param.is_optional() && !param.has_default() && !param.is_spread()) {
+            return Err(err!(MISSING_ARG, call.args.range, name));
+        }
+        let map = type_params.iter().map(|param| param.id).zip(type_args).collect::<Vec<(u32, SymbolRef)>>();
+        for (ind, arg) in args.iter().enumerate() {
+            if inferred_from.contains(&ind) { continue };
+            let param = self.substitute(&parameters[ind.min(parameters.len() - 1)].1, &map)?;
+            self.check_exp_assignable(module, arg, &param)?;
+        }
+        Ok(if is_inferred { Some(self.substitute(&return_type, &map)?) } else { None })
+    }
+
+    // Only enum variants are checked for now, the type of any other mod access expression cannot be inferred
+    fn check_mod_access_exp(&mut self, module: &mut Module, access: &ASTModAccess, expected: Option<&SymbolRef>) -> LazyResult<Option<SymbolRef>> {
+        let mut current = self.get_sym_from_var(module, &access.path[0])?;
+        for (ind, var) in access.path.iter().enumerate().skip(1) {
+            let sym = current.to_symbol(self);
+            if let SymbolKind::Enum(variants) = &sym.kind {
+                let payload = variants.get(&var.value).cloned().ok_or_else(|| err!(NAME_NOT_FOUND, var.range, &var.value))?;
+                if let Some(next) = access.path.get(ind + 1) {
+                    return Err(err!(NAME_NOT_FOUND, next.range, &next.value));
+                }
+                return self.check_variant(module, current, var, payload, access, expected).map(Some);
+            }
+            current = sym.get_mod_type(self, &var.value).cloned().ok_or_else(|| err!(NAME_NOT_FOUND, var.range, &var.value))?;
+        }
+        Ok(None)
+    }
+
+    // Checks the value a variant is constructed with, and returns the type of the enum. The type arguments of generic
+    // enums are inferred from the expected type, or from the value if there's no expected instance of the enum.
+    fn check_variant(&mut self, module: &mut Module, enumeration: SymbolRef, variant: &ASTVar, payload: SymbolRef, access: &ASTModAccess, expected: Option<&SymbolRef>) -> LazyResult<SymbolRef> {
+        let values = access.init.as_ref().map_or(&[] as &[ASTExpression], |init| &init.expressions);
+        let takes_value = payload.id != self.primitives["none"] || payload.is_optional();
+        if values.len() != takes_value as usize {
+            return Err(err!(INVALID_AMOUNT_OF_VALUES, access.range, &variant.value, &(takes_value as usize).to_string(), &values.len().to_string()));
+        }
+        let type_params = enumeration.to_symbol(self).type_params.clone();
+        let expected = expected.map(|expected| self.resolve_alias(expected.clone()));
+        let (enumeration, type_args) = if let Some(typings) = &access.typings {
+            let type_args = self.check_list(module, typings)?;
+            let instance = self.instantiate(enumeration.id, type_args.clone(), typings.range)?;
+            self.check_bounds(enumeration.id, &type_args, typings.range)?;
+            (instance, type_args)
+        } else if type_params.is_empty() {
+            (enumeration, vec![])
+        } else if let Some(instance_id) = expected.as_ref().filter(|expected| expected.id == enumeration.id).and_then(|expected| expected.instance_id) {
+            let type_args = enumeration.to_symbol(self).instances[instance_id].type_args.clone();
+            (SymbolRef::new_instance(enumeration.id, instance_id), type_args)
+        } else {
+            match values.first() {
+                // The type of the value is the type argument, so the value doesn't have to be checked again
+                Some(value) if type_params.len() == 1 && payload.id == type_params[0].id && !payload.is_optional() => {
+                    return match self.get_sym_from_exp(module, value)? {
+                        Some(arg) => {
+                            let instance = self.instantiate(enumeration.id, vec![arg.clone()], access.range)?;
+                            self.check_bounds(enumeration.id, std::slice::from_ref(&arg), access.range)?;
+                            Ok(instance)
+                        },
+                        None => Ok(enumeration)
+                    };
+                },
+                _ => (enumeration, vec![])
+            }
+        };
+        if let Some(value) = values.first() {
+            // Type parameters accept anything, unless the enum is given type arguments or they're inferred
+            let map = type_params.iter().map(|param| param.id).zip(type_args).collect::<Vec<(u32, SymbolRef)>>();
+            let expected = self.substitute(&payload, &map)?;
+            self.check_exp_assignable(module, value, &expected)?;
+        }
+        Ok(enumeration)
+    }
+
+    // The values of the fields have to be assignable to the fields
+    fn check_init(&mut self, module: &mut Module, init: &ASTInitializor) -> LazyResult<SymbolRef> {
+        let target = self.get_sym_from_mod_access_values(module, &init.target)?;
+        let props = match target.get_kind(self) {
+            SymbolKind::Struct(props) => props.clone(),
+            _ => return Ok(target)
+        };
+        for (name, value, range) in &init.params.pairs {
+            let prop = props.get(name).ok_or_else(|| err!(NO_MEMBER, *range, name, &self.type_name(&target)))?;
+            if let Some(value) = value {
+                self.check_exp_assignable(module, value, &prop.kind)?;
+            }
+        }
+        // Fields which are static, optional, methods or have a default value can be left out
+        if let StatementOrExpression::StructStatement(decl) = &target.to_symbol(self).declaration {
+            let given = init.params.pairs.iter().map(|(name, _, _)| name.as_str()).collect::<Vec<&str>>();
+            let missing = decl.fields.pairs.iter().find(|field| {
+                let is_method = matches!(&field.value, Some(ASTTypings::Function(func)) if func.body.is_some());
+                !given.contains(&field.name.as_str()) && field.default_value.is_none() && !is_method && !field.modifiers.contains(ASTModifiers::STATIC) && !props[&field.name].kind.is_optional()
+            });
+            if let Some(field) = missing {
+                return Err(err!(MISSING_FIELD, init.params.range, &format!("{{{}}}", given.join(", ")), &self.type_name(&target), &field.name));
+            }
+        }
+        Ok(target)
+    }
+
+    fn check_exp_assignable(&mut self, module: &mut Module, exp: &ASTExpression, to: &SymbolRef) -> LazyResult<()> {
+        let resolved = self.resolve_alias(to.clone());
+        match exp {
+            // Number literals don't have a type of their own
+            ASTExpression::Int(_) if self.is_number(&resolved) => return Ok(()),
+            ASTExpression::Unary(unary) if unary.op == "-" && matches!(*unary.value, ASTExpression::Int(_) | ASTExpression::Float(_)) => return self.check_exp_assignable(module, &unary.value, to),
+            ASTExpression::Block(block) => return self.check_block(module, block, Some(to)).map(|_| ()),
+            ASTExpression::If(if_exp) => return self.check_if(module, if_exp, Some(to)).map(|_| ()),
+            ASTExpression::Match(match_exp) => return self.check_match(module, match_exp, Some(to)).map(|_| ()),
+            // Functions get the types of their parameters and their return type from the expected type
+            ASTExpression::Function(func) => return match self.check_function(module, func, true, Some(to))? {
+                Some(found) => self.check_assignable(&found, to, full_expression_range(exp)),
+                None => Ok(())
+            },
+            // The type arguments of variants are inferred from the expected type
+            ASTExpression::ModAccess(access) => return match self.check_mod_access_exp(module, access, Some(to))? {
+                Some(found) => self.check_assignable(&found, to, full_expression_range(exp)),
+                None => Ok(())
             },
-            _ => {
-                if let Some(sym) = module.get_sym(&var.value) {
-                    Ok(sym.id)
-                } else {
-                    Err(err!(NAME_NOT_FOUND, var.range, &module.filename, &var.value))
+            ASTExpression::Tuple(list) => {
+                if let SymbolKind::Tuple(types) = resolved.get_kind(self) {
+                    if types.len() == list.expressions.len() {
+                        for (element, typing) in list.expressions.iter().zip(types.clone()) {
+                            self.check_exp_assignable(module, element, &typing)?;
+                        }
+                        return Ok(());
+                    }
+                }
+            },
+            _ => {}
+        }
+        match self.get_sym_from_exp(module, exp)? {
+            Some(found) => self.check_assignable(&found, to, full_expression_range(exp)),
+            None => Ok(())
+        }
+    }
+
+    fn is_number(&self, reference: &SymbolRef) -> bool {
+        NUMBERS.iter().any(|name| self.primitives[*name] == reference.id)
+    }
+
+    // Like `is_assignable`, but explains why the type isn't assignable
+    fn check_assignable(&self, from: &SymbolRef, to: &SymbolRef, range: Range) -> LazyResult<()> {
+        if self.is_assignable(from, to) {
+            return Ok(());
+        }
+        let (resolved_from, resolved_to) = (self.resolve_alias(from.clone()), self.resolve_alias(to.clone()));
+        if let (SymbolKind::Struct(required), Some(members)) = (resolved_to.get_kind(self), self.get_members(&resolved_from)) {
+            if self.is_anonymous(&resolved_to) && (resolved_to.is_optional() || !resolved_from.is_optional()) {
+                let mut required = required.iter().collect::<Vec<(&String, &SymbolProperty)>>();
+                required.sort_by_key(|(name, _)| *name);
+                for (name, prop) in required {
+                    match members.get(name) {
+                        None => return Err(err!(MISSING_FIELD, range, &self.type_name(from), &self.type_name(to), name)),
+                        Some(member) if !self.is_assignable(member, &prop.kind) => return Err(err!(WRONG_FIELD_TYPE, range, &self.type_name(from), &self.type_name(to), name)),
+                        _ => {}
+                    }
                 }
             }
         }
+        Err(err!(MISMATCHED_TYPES, range, &self.type_name(to), &self.type_name(from)))
+    }
+
+    // Structs are only assignable to themselves and to partials (structs without a name) whose members they have.
+    // Functions are assignable if they accept the parameters and return the return type, a function which returns
+    // `none` accepts any return type.
+    pub fn is_assignable(&self, from: &SymbolRef, to: &SymbolRef) -> bool {
+        // Aliases which were used before they were resolved
+        let (from, to) = (&self.resolve_alias(from.clone()), &self.resolve_alias(to.clone()));
+        let none = self.primitives["none"];
+        if to.is_type_param() {
+            return true;
+        }
+        if to.is_optional() {
+            if from.id == none { return true };
+        } else if from.is_optional() {
+            return false;
+        }
+        if from.id == to.id && from.instance_id == to.instance_id {
+            return true;
+        }
+        match (from.get_kind(self), to.get_kind(self)) {
+            (_, SymbolKind::Struct(required)) if self.is_anonymous(to) => match self.get_members(from) {
+                Some(members) => required.iter().all(|(name, prop)| members.get(name).is_some_and(|member| self.is_assignable(member, &prop.kind))),
+                None => false
+            },
+            (SymbolKind::Tuple(from_types), SymbolKind::Tuple(to_types)) => from_types.len() == to_types.len() && from_types.iter().zip(to_types).all(|(from, to)| self.is_assignable(from, to)),
+            (SymbolKind::Fn { parameters: from_params, return_type: from_return }, SymbolKind::Fn { parameters: to_params, return_type: to_return }) => {
+                // Parameters are contravariant, and parameters which aren't passed must be optional
+                from_params.iter().enumerate().all(|(ind, (_, from_param))| match to_params.get(ind) {
+                    Some((_, to_param)) => self.is_assignable(to_param, from_param),
+                    None => from_param.is_optional()
+                }) && ((to_return.id == none && !to_return.is_optional()) || self.is_assignable(from_return, to_return))
+            },
+            _ => false
+        }
     }
 
-    fn check_list(&mut self, module: &mut Module, list: &ASTListTyping, handle_temps: bool) -> LazyResult<Vec<SymbolRef>> {
+    fn is_anonymous(&self, reference: &SymbolRef) -> bool {
+        matches!(reference.to_symbol(self).declaration, StatementOrExpression::None)
+    }
+
+    // How the type is written in the source code
+    pub fn type_name(&self, reference: &SymbolRef) -> String {
+        let sym = reference.to_symbol(self);
+        let mut name = match reference.instance_id {
+            Some(id) => format!("{}<{}>", sym.name, sym.instances[id].type_args.iter().map(|arg| self.type_name(arg)).collect::<Vec<String>>().join(", ")),
+            None => sym.name.clone()
+        };
+        if reference.is_optional() { name.push('?') };
+        name
+    }
+
+    fn get_sym_from_var(&mut self, module: &mut Module, var: &ASTVar) -> LazyResult<SymbolRef> {
+        if let Some(param) = self.type_params.get(&var.value) {
+            return Ok(param.clone());
+        }
+        if module.temporary.contains_key(&var.value) {
+            let reference = self.check_declaration(module, &var.value)?;
+            if let SymbolKind::Static(_) = reference.get_kind(self) {
+                return Err(err!(VAL_AS_TYPE, var.range));
+            }
+            return Ok(reference);
+        }
+        if let Some(sym) = module.get_sym(&var.value) {
+            if let StatementOrExpression::StaticStatement(_) = sym.to_symbol(self).declaration {
+                return Err(err!(VAL_AS_TYPE, var.range));
+            }
+            if let Some(start) = self.alias_stack.iter().position(|(id, _)| *id == sym.id) {
+                let path = self.alias_stack[start..].iter().map(|(_, name)| name.as_str()).chain([var.value.as_str()]).collect::<Vec<&str>>().join(" -> ");
+                return Err(err!(CYCLIC_ALIAS, var.range, &var.value, &path; ["Use a struct or an enum to declare recursive types."]));
+            }
+            return Ok(self.resolve_alias(sym.clone()));
+        }
+        if let Some(id) = self.primitives.get(&var.value) {
+            return Ok(SymbolRef::new_ref(*id));
+        }
+        Err(err!(NAME_NOT_FOUND, var.range, &var.value))
+    }
+
+    fn check_list(&mut self, module: &mut Module, list: &ASTListTyping) -> LazyResult<Vec<SymbolRef>> {
         let mut result: Vec<SymbolRef> = Vec::new();
         for typing in &list.entries {
-            result.push(self.get_sym_from_type(module, typing, handle_temps)?);
+            result.push(self.get_sym_from_type(module, typing)?);
         }
         Ok(result)
     }
 
-}
\ No newline at end of file
+}
+
+fn is_int_literal(exp: &ASTExpression) -> bool {
+    match exp {
+        ASTExpression::Int(_) => true,
+        ASTExpression::Unary(unary) => unary.op == "-" && matches!(*unary.value, ASTExpression::Int(_)),
+        _ => false
+    }
+}
+
+// The field of a struct pattern, `{ a }` and `{ a: b }` both take the field `a`
+fn deconstructed_field(item: &ASTDeconstructItem) -> Option<&ASTVar> {
+    match (&item.field, &item.pattern) {
+        (Some(field), _) | (None, ASTDeclareTypes::Var(field)) => Some(field),
+        _ => None
+    }
+}
@@ -1,28 +1,45 @@
 
-use crate::{module::Module};
+use crate::{module::Module, lints::LintConfig, checker::TypeChecker};
 use std::collections::HashMap;
-use errors::{builder::ErrorFormatter, LazyMultiResult};
+use errors::{builder::ErrorFormatter, LazyMultiResult, Range, SourceMap};
 use std::fs;
+use parser::ast::DEFAULT_MAX_DEPTH;
 use crate::path::full_path;
 
 pub trait FileHost: ErrorFormatter {
     fn create(&mut self, path: &str) -> LazyMultiResult<Option<&Module>>;
     fn get(&self, path: &str) -> Option<&Module>;
     fn get_or_create(&mut self, path: &str) -> LazyMultiResult<Option<&Module>>;
-    fn get_unique_id(&mut self) -> u32;
+    fn checker(&mut self) -> &mut TypeChecker;
+    fn get_unique_id(&mut self) -> u32 {
+        self.checker().get_unique_id()
+    }
+    // The modules which are currently importing another module, and the range of the path in the import statement
+    fn import_stack(&mut self) -> &mut Vec<(String, Range)>;
+    fn source_map_mut(&mut self) -> &mut SourceMap;
+    fn lint_config(&self) -> &LintConfig;
+    // The maximum nesting depth the parser allows
+    fn max_depth(&self) -> usize;
+    // Turns the path of an import to the name the module is saved under
+    fn normalize_path(&self, path: &str) -> String {
+        path.to_string()
+    }
 }
 
 pub struct VirtualFileHost {
-    pub id_counter: u32,
+    pub checker: TypeChecker,
     pub files: HashMap<String, Module>,
-    pub file_contents: HashMap<String, String>,
+    pub sources: SourceMap,
     pub file_cache: HashMap<String, String>,
+    pub imports: Vec<(String, Range)>,
+    pub lints: LintConfig,
+    pub max_depth: usize
 }
 
 impl ErrorFormatter for VirtualFileHost {
 
-    fn get_file_contents(&self, file: &str) -> Option<&str> {
-        Some(&self.file_contents.get(file)?)
+    fn source_map(&self) -> &SourceMap {
+        &self.sources
     }
 }
 
@@ -32,17 +49,35 @@ impl FileHost for VirtualFileHost {
         self.files.get(path)
     }
 
+    // The cached source is kept, so a module which failed to be checked is checked again the next time it's imported
     fn get_or_create(&mut self, path: &str) -> LazyMultiResult<Option<&Module>> {
-        if let Some(file_contents) = self.file_cache.remove(path) {
+        if self.files.contains_key(path) {
+            Ok(self.files.get(path))
+        } else if let Some(file_contents) = self.file_cache.get(path).cloned() {
             self.create_virtual(path, file_contents)
         } else {
-            Ok(self.files.get(path))
+            Ok(None)
         }
     }
 
-    fn get_unique_id(&mut self) -> u32 {
-        self.id_counter += 1;
-        self.id_counter
+    fn checker(&mut self) -> &mut TypeChecker {
+        &mut self.checker
+    }
+
+    fn import_stack(&mut self) -> &mut Vec<(String, Range)> {
+        &mut self.imports
+    }
+
+    fn source_map_mut(&mut self) -> &mut SourceMap {
+        &mut self.sources
+    }
+
+    fn lint_config(&self) -> &LintConfig {
+        &self.lints
+    }
+
+    fn max_depth(&self) -> usize {
+        self.max_depth
     }
 
     fn create(&mut self, _path: &str) -> LazyMultiResult<Option<&Module>> {
@@ -55,10 +90,13 @@ impl VirtualFileHost {
 
     pub fn new() -> Self {
         Self {
-            id_counter: 0,
+            checker: TypeChecker::new(),
             files: HashMap::new(),
-            file_contents: HashMap::new(),
-            file_cache: HashMap::new()
+            sources: SourceMap::new(),
+            file_cache: HashMap::new(),
+            imports: vec![],
+            lints: LintConfig::new(),
+            max_depth: DEFAULT_MAX_DEPTH
         }
     }
 
@@ -67,7 +105,6 @@ impl VirtualFileHost {
     }
 
     pub fn create_virtual(&mut self, path: &str, content: String) -> LazyMultiResult<Option<&Module>> {
-        self.file_contents.insert(path.to_string(), content.clone());
         let module = Module::from_str(self, path, &content)?;
         self.files.insert(path.to_string(), module);
         Ok(self.files.get(path))
@@ -75,36 +112,57 @@ impl VirtualFileHost {
 }
 
 pub struct FSFileHost {
-    pub id_counter: u32,
+    pub checker: TypeChecker,
     pub files: HashMap<String, Module>,
-    pub file_contents: HashMap<String, String>,
+    pub sources: SourceMap,
+    pub imports: Vec<(String, Range)>,
+    pub lints: LintConfig,
+    pub max_depth: usize
 }
 
 impl ErrorFormatter for FSFileHost {
 
-    fn get_file_contents(&self, file: &str) -> Option<&str> {
-        Some(&self.file_contents.get(file)?)
+    fn source_map(&self) -> &SourceMap {
+        &self.sources
     }
 }
 
 impl FileHost for FSFileHost {
 
-    fn get_unique_id(&mut self) -> u32 {
-        self.id_counter += 1;
-        self.id_counter
+    fn checker(&mut self) -> &mut TypeChecker {
+        &mut self.checker
     }
 
     fn get(&self, path: &str) -> Option<&Module> {
         self.files.get(path)
     }
 
-    fn create(&mut self, path: &str) -> LazyMultiResult<Option<&Module>> {
+    fn import_stack(&mut self) -> &mut Vec<(String, Range)> {
+        &mut self.imports
+    }
+
+    fn source_map_mut(&mut self) -> &mut SourceMap {
+        &mut self.sources
+    }
+
+    fn lint_config(&self) -> &LintConfig {
+        &self.lints
+    }
+
+    fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    fn normalize_path(&self, path: &str) -> String {
         let mut full_path = full_path(path);
         if !full_path.ends_with(".lazy") { full_path += ".lazy" };
+        full_path
+    }
+
+    fn create(&mut self, path: &str) -> LazyMultiResult<Option<&Module>> {
+        let full_path = self.normalize_path(path);
         if let Ok(text) = fs::read_to_string(&full_path) {
-            let module = Module::from_str(self, &full_path, &text);
-            self.file_contents.insert(full_path.to_string(), text);
-            let module = module?;
+            let module = Module::from_str(self, &full_path, &text)?;
             self.files.insert(full_path.clone(), module);
             Ok(self.files.get(&full_path))
         } else {
@@ -113,10 +171,11 @@ impl FileHost for FSFileHost {
     }
 
     fn get_or_create(&mut self, path: &str) -> LazyMultiResult<Option<&Module>> {
-        if self.files.contains_key(path) {
-            Ok(self.files.get(path))
+        let full_path = self.normalize_path(path);
+        if self.files.contains_key(&full_path) {
+            Ok(self.files.get(&full_path))
         } else {
-            self.create(path)
+            self.create(&full_path)
         }
     }
 
@@ -126,9 +185,12 @@ impl FSFileHost {
 
     pub fn new() -> Self {
         Self {
-            id_counter: 0,
+            checker: TypeChecker::new(),
             files: HashMap::new(),
-            file_contents: HashMap::new()
+            sources: SourceMap::new(),
+            imports: vec![],
+            lints: LintConfig::new(),
+            max_depth: DEFAULT_MAX_DEPTH
         }
     }
 }
\ No newline at end of file
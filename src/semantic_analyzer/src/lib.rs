@@ -3,4 +3,5 @@ pub mod symbol;
 pub mod file_host;
 pub mod module;
 pub mod path;
-pub mod checker;
\ No newline at end of file
+pub mod checker;
+pub mod lints;
\ No newline at end of file
@@ -0,0 +1,606 @@
+use std::collections::{HashMap, HashSet};
+use parser::{ast::{model::*, utils::full_expression_range}, tokenizer::TokenType};
+use errors::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny
+}
+
+impl LintLevel {
+
+    // The names used in attributes (`#allow(dead_code)`) and in the project configuration
+    pub fn parse(level: &str) -> Option<Self> {
+        match level {
+            "allow" => Some(Self::Allow),
+            "warn" => Some(Self::Warn),
+            "deny" => Some(Self::Deny),
+            _ => None
+        }
+    }
+}
+
+// A non-fatal check. Every lint reports exactly one diagnostic, and its level decides the severity of the diagnostic.
+pub struct Lint {
+    pub name: &'static str,
+    pub code: u16,
+    pub default_level: LintLevel,
+    pub description: &'static str
+}
+
+pub struct Lints;
+
+impl Lints {
+    pub const UNUSED_IMPORTS: Lint = Lint {
+        name: "unused_imports",
+        code: Diagnostics::UNUSED_IMPORT.code,
+        default_level: LintLevel::Warn,
+        description: "Imported names which are never used"
+    };
+    pub const UNUSED_VARIABLES: Lint = Lint {
+        name: "unused_variables",
+        code: Diagnostics::UNUSED_VARIABLE.code,
+        default_level: LintLevel::Warn,
+        description: "Variables and parameters which are never used"
+    };
+    pub const SHADOWED_NAMES: Lint = Lint {
+        name: "shadowed_names",
+        code: Diagnostics::SHADOWED_NAME.code,
+        default_level: LintLevel::Warn,
+        description: "Variables with the same name as a variable from an outer scope"
+    };
+    pub const NON_SNAKE_CASE_FIELDS: Lint = Lint {
+        name: "non_snake_case_fields",
+        code: Diagnostics::NON_SNAKE_CASE_FIELD.code,
+        default_level: LintLevel::Warn,
+        description: "Struct fields which aren't snake_case"
+    };
+    pub const NON_CAMEL_CASE_TYPES: Lint = Lint {
+        name: "non_camel_case_types",
+        code: Diagnostics::NON_CAMEL_CASE_TYPE.code,
+        default_level: LintLevel::Warn,
+        description: "Structs, enums, enum variants and type aliases which aren't UpperCamelCase"
+    };
+    pub const UNUSED_TYPE_PARAMS: Lint = Lint {
+        name: "unused_type_params",
+        code: Diagnostics::UNUSED_TYPE_PARAM.code,
+        default_level: LintLevel::Warn,
+        description: "Type parameters which are never used"
+    };
+    pub const DEAD_CODE: Lint = Lint {
+        name: "dead_code",
+        code: Diagnostics::DEAD_CODE.code,
+        default_level: LintLevel::Warn,
+        description: "Structs, enums and type aliases which aren't exported or used"
+    };
+    pub const SELF_ASSIGNMENT: Lint = Lint {
+        name: "self_assignment",
+        code: Diagnostics::SELF_ASSIGNMENT.code,
+        default_level: LintLevel::Warn,
+        description: "Assigning a variable or a property to itself"
+    };
+    pub const POINTLESS_TEMPLATE: Lint = Lint {
+        name: "pointless_template",
+        code: Diagnostics::POINTLESS_TEMPLATE.code,
+        default_level: LintLevel::Warn,
+        description: "Template literals without interpolations"
+    };
+    pub const UNKNOWN_LINTS: Lint = Lint {
+        name: "unknown_lints",
+        code: Diagnostics::UNKNOWN_LINT.code,
+        default_level: LintLevel::Warn,
+        description: "Lint attributes with names which don't exist"
+    };
+
+    pub const ALL: &'static [Lint] = &[Self::UNUSED_IMPORTS, Self::UNUSED_VARIABLES, Self::SHADOWED_NAMES, Self::NON_SNAKE_CASE_FIELDS, Self::NON_CAMEL_CASE_TYPES, Self::UNUSED_TYPE_PARAMS, Self::DEAD_CODE, Self::SELF_ASSIGNMENT, Self::POINTLESS_TEMPLATE, Self::UNKNOWN_LINTS];
+
+    pub fn from_name(name: &str) -> Option<&'static Lint> {
+        Self::ALL.iter().find(|lint| lint.name == name)
+    }
+
+    pub fn from_code(code: u16) -> Option<&'static Lint> {
+        Self::ALL.iter().find(|lint| lint.code == code)
+    }
+}
+
+// The project-level lint levels, which override the default levels
+#[derive(Clone, Default)]
+pub struct LintConfig {
+    levels: HashMap<&'static str, LintLevel>
+}
+
+impl LintConfig {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Returns false if there's no lint with the name
+    pub fn set(&mut self, name: &str, level: LintLevel) -> bool {
+        if let Some(lint) = Lints::from_name(name) {
+            self.levels.insert(lint.name, level);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn level(&self, lint: &Lint) -> LintLevel {
+        self.levels.get(lint.name).copied().unwrap_or(lint.default_level)
+    }
+}
+
+// The lint levels inside a single module
+pub struct LintContext {
+    config: LintConfig,
+    // The range of every statement with a lint attribute, and the level the attribute sets
+    scopes: Vec<(Range, &'static Lint, LintLevel)>
+}
+
+impl LintContext {
+
+    pub fn new(config: &LintConfig, ast: &[ASTStatement], errors: &mut ErrorCollector) -> Self {
+        let mut ctx = Self { config: config.clone(), scopes: vec![] };
+        for statement in ast {
+            ctx.collect_attributes(statement, errors);
+        }
+        ctx
+    }
+
+    fn collect_attributes(&mut self, statement: &ASTStatement, errors: &mut ErrorCollector) {
+        match statement {
+            ASTStatement::Meta(meta) => {
+                if let Some(level) = LintLevel::parse(&meta.name.value) {
+                    for arg in &meta.args {
+                        let name = match arg {
+                            TokenType::Var(name) | TokenType::Str(name) => name.clone(),
+                            other => other.to_string()
+                        };
+                        if let Some(lint) = Lints::from_name(&name) {
+                            self.scopes.push((meta.range, lint, level));
+                        } else {
+                            errors.push(err!(UNKNOWN_LINT, meta.name.range, &name).with_severity(Severity::Warning));
+                        }
+                    }
+                }
+                self.collect_attributes(&meta.target, errors);
+            },
+            ASTStatement::Export(decl) => self.collect_attributes(&decl.value, errors),
+            _ => {}
+        }
+    }
+
+    // Attributes on inner statements win over attributes on outer ones, and both win over the configuration
+    pub fn level(&self, lint: &Lint, range: Range) -> LintLevel {
+        self.scopes.iter()
+            .filter(|(scope, scope_lint, _)| scope_lint.code == lint.code && scope.start.pos <= range.start.pos && range.end.pos <= scope.end.pos)
+            .max_by_key(|(scope, _, _)| scope.start.pos)
+            .map_or_else(|| self.config.level(lint), |(_, _, level)| *level)
+    }
+
+    // Changes the severity of every lint diagnostic to the level of its lint, and removes the allowed ones
+    pub fn apply(&self, errors: &mut ErrorCollector) {
+        errors.collected.retain_mut(|err| {
+            let lint = if let Some(lint) = err.code.and_then(Lints::from_code) { lint } else { return true };
+            match self.level(lint, err.range) {
+                LintLevel::Allow => false,
+                LintLevel::Warn => {
+                    err.severity = Severity::Warning;
+                    true
+                },
+                LintLevel::Deny => {
+                    err.severity = Severity::Error;
+                    true
+                }
+            }
+        });
+    }
+}
+
+fn is_snake_case(name: &str) -> bool {
+    !name.chars().any(|ch| ch.is_uppercase())
+}
+
+fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<char>>();
+    let mut res = String::new();
+    for (ind, ch) in chars.iter().enumerate() {
+        if ch.is_uppercase() && ind != 0 {
+            // Acronyms stay together - `HTTPCode` becomes `http_code`
+            let prev = chars[ind - 1];
+            let next_is_lower = chars.get(ind + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower) { res.push('_') };
+        }
+        res.extend(ch.to_lowercase());
+    }
+    res
+}
+
+fn is_camel_case(name: &str) -> bool {
+    let name = name.trim_start_matches('_');
+    name.chars().next().is_none_or(|ch| !ch.is_lowercase()) && !name.contains('_')
+}
+
+fn to_camel_case(name: &str) -> String {
+    name.split('_').filter(|part| !part.is_empty()).map(|part| {
+        let mut chars = part.chars();
+        chars.next().map_or(String::new(), |first| first.to_uppercase().chain(chars).collect())
+    }).collect()
+}
+
+struct Local {
+    name: String,
+    range: Range,
+    used: bool
+}
+
+// Walks a single statement, and reports the lints which don't need information from other statements
+struct LintChecker<'a> {
+    errors: &'a mut ErrorCollector,
+    scopes: Vec<Vec<Local>>,
+    // Names which are used, but aren't local variables
+    refs: HashSet<String>
+}
+
+impl<'a> LintChecker<'a> {
+
+    fn warn(&mut self, err: BaseError) {
+        self.errors.push(err.with_severity(Severity::Warning));
+    }
+
+    fn use_name(&mut self, name: &str) {
+        for scope in self.scopes.iter_mut().rev() {
+            if let Some(local) = scope.iter_mut().rev().find(|local| local.name == name) {
+                local.used = true;
+                return;
+            }
+        }
+        self.refs.insert(name.to_string());
+    }
+
+    fn declare(&mut self, var: &ASTVar) {
+        if self.scopes.is_empty() { self.scopes.push(vec![]) };
+        let outer = &self.scopes[..self.scopes.len() - 1];
+        if !var.value.starts_with('_') && outer.iter().any(|scope| scope.iter().any(|local| local.name == var.value)) {
+            self.warn(err!(SHADOWED_NAME, var.range, &var.value));
+        }
+        self.scopes.last_mut().unwrap().push(Local { name: var.value.clone(), range: var.range, used: false });
+    }
+
+    fn pop_scope(&mut self) {
+        for local in self.scopes.pop().unwrap_or_default() {
+            if !local.used && !local.name.starts_with('_') {
+                self.warn(err!(UNUSED_VARIABLE, local.range, &local.name));
+            }
+        }
+    }
+
+    // Type parameters are checked against the names used inside the declaration only
+    fn with_type_params(&mut self, params: &[ASTTypeParameter], visit: impl FnOnce(&mut Self)) {
+        let outer_refs = std::mem::take(&mut self.refs);
+        visit(self);
+        for param in params {
+            if !self.refs.contains(&param.name.value) && !param.name.value.starts_with('_') {
+                self.warn(err!(UNUSED_TYPE_PARAM, param.name.range, &param.name.value));
+            }
+        }
+        for param in params {
+            if let Some(constraint) = &param.constraint { self.typing(constraint) };
+        }
+        for param in params {
+            self.refs.remove(&param.name.value);
+        }
+        self.refs.extend(outer_refs);
+    }
+
+    fn type_name(&mut self, name: &ASTVar) {
+        if !is_camel_case(&name.value) {
+            self.warn(err!(NON_CAMEL_CASE_TYPE, name.range, &name.value, &to_camel_case(&name.value)));
+        }
+    }
+
+    fn statement(&mut self, statement: &ASTStatement) {
+        match statement {
+            ASTStatement::EnumDeclaration(decl) => {
+                self.type_name(&decl.name);
+                for variant in &decl.values.pairs {
+                    if !is_camel_case(&variant.name) {
+                        self.warn(err!(NON_CAMEL_CASE_TYPE, variant.name_range, &variant.name, &to_camel_case(&variant.name)));
+                    }
+                }
+                self.with_type_params(&decl.typings, |this| this.pair_list(&decl.values));
+            },
+            ASTStatement::Struct(decl) => {
+                self.type_name(&decl.name);
+                for field in &decl.fields.pairs {
+                    if !is_snake_case(&field.name) {
+                        self.warn(err!(NON_SNAKE_CASE_FIELD, field.name_range, &field.name, &to_snake_case(&field.name)));
+                    }
+                }
+                self.with_type_params(&decl.typings, |this| this.pair_list(&decl.fields));
+            },
+            ASTStatement::Type(decl) => {
+                self.type_name(&decl.name);
+                self.with_type_params(&decl.typings, |this| this.typing(&decl.value));
+            },
+            ASTStatement::Static(decl) => {
+                if let Some(typings) = &decl.typings { self.list_typing(typings) };
+                self.expression(&decl.value);
+            },
+            ASTStatement::Main(main) => self.block(&main.expression),
+            ASTStatement::Export(decl) => self.statement(&decl.value),
+            ASTStatement::Meta(meta) => self.statement(&meta.target),
+            ASTStatement::Impl(decl) => {
+                self.mod_access_value(&decl.partial);
+                self.mod_access_value(&decl.target);
+                if let Some(typings) = &decl.typings { self.list_typing(typings) };
+                self.pair_list(&decl.fields);
+            },
+            ASTStatement::Import(_) => {}
+        }
+    }
+
+    fn pair_list(&mut self, list: &ASTPairListTyping) {
+        for pair in &list.pairs {
+            if let Some(typing) = &pair.value { self.typing(typing) };
+            if let Some(default_value) = &pair.default_value { self.expression(default_value) };
+        }
+    }
+
+    fn list_typing(&mut self, list: &ASTListTyping) {
+        for typing in &list.entries {
+            self.typing(typing);
+        }
+    }
+
+    fn typing(&mut self, typing: &ASTTypings) {
+        match typing {
+            ASTTypings::Var(var) => self.var_typing(var),
+            ASTTypings::Mod(access) => self.mod_access(access),
+            ASTTypings::PairList(list) => self.pair_list(list),
+            ASTTypings::Function(func) => self.function(func),
+            ASTTypings::Optional(typing) => self.typing(typing),
+            ASTTypings::Tuple(list) => self.list_typing(list),
+            ASTTypings::Combine(combine) => {
+                self.typing(&combine.left);
+                self.typing(&combine.right);
+            },
+            ASTTypings::Impl(implementation) => self.typing(&implementation.value)
+        }
+    }
+
+    fn var_typing(&mut self, var: &ASTVarTyping) {
+        self.use_name(&var.value.value);
+        if let Some(typings) = &var.typings { self.list_typing(typings) };
+    }
+
+    fn mod_access(&mut self, access: &ASTModAccess) {
+        if let Some(first) = access.path.first() { self.use_name(&first.value) };
+        if let Some(typings) = &access.typings { self.list_typing(typings) };
+        if let Some(init) = &access.init { self.expression_list(init) };
+    }
+
+    fn mod_access_value(&mut self, value: &ASTModAccessValues) {
+        match value {
+            ASTModAccessValues::ModAccess(access) => self.mod_access(access),
+            ASTModAccessValues::Var(var) => self.var_typing(var)
+        }
+    }
+
+    fn function(&mut self, func: &ASTFunction) {
+        self.with_type_params(&func.typings, |this| {
+            this.pair_list(&func.params);
+            if let Some(return_type) = &func.return_type { this.typing(return_type) };
+            // Parameters of function types don't have a body which could use them
+            if let Some(body) = &func.body {
+                this.scopes.push(func.params.pairs.iter().map(|param| Local { name: param.name.clone(), range: param.name_range, used: false }).collect());
+                this.expression(body);
+                this.pop_scope();
+            }
+        });
+    }
+
+    fn block(&mut self, block: &ASTBlock) {
+        self.scopes.push(vec![]);
+        for element in &block.elements {
+            self.expression(element);
+        }
+        self.pop_scope();
+    }
+
+    fn expression_list(&mut self, list: &ASTExpressionList) {
+        for exp in &list.expressions {
+            self.expression(exp);
+        }
+    }
+
+    fn pattern(&mut self, pattern: &ASTDeclareTypes) {
+        match pattern {
+            ASTDeclareTypes::Var(var) => self.declare(var),
+            ASTDeclareTypes::TupleDeconstruct(list) | ASTDeclareTypes::StructDeconstruct(list) => {
+                for item in &list.values {
+                    if let Some(default_value) = &item.default_value { self.expression(default_value) };
+                    self.pattern(&item.pattern);
+                }
+            }
+        }
+    }
+
+    // Chains like `a + b + c` and `a.b().c()` are as deep as they are long, so their left side is walked with a loop. The
+    // links are visited from the innermost one, so names are still visited in the order they're written in.
+    fn expression(&mut self, exp: &ASTExpression) {
+        let mut chain: Vec<&ASTExpression> = vec![];
+        let mut current = exp;
+        loop {
+            let next = match current {
+                ASTExpression::Binary(bin) => {
+                    if bin.op == "=" { self.self_assignment(bin, full_expression_range(current)) };
+                    &bin.left
+                },
+                ASTExpression::Unary(un) => &un.value,
+                ASTExpression::DotAccess(access) => &access.value,
+                ASTExpression::IndexAccess(access) => &access.value,
+                ASTExpression::Optional(op) => &op.value,
+                ASTExpression::Call(call) => &call.target,
+                _ => break
+            };
+            chain.push(current);
+            current = next;
+        }
+        self.expression_part(current);
+        for link in chain.into_iter().rev() {
+            match link {
+                ASTExpression::Binary(bin) => self.expression(&bin.right),
+                ASTExpression::IndexAccess(access) => self.expression(&access.target),
+                ASTExpression::Call(call) => {
+                    if let Some(typings) = &call.typings { self.list_typing(typings) };
+                    self.expression_list(&call.args);
+                },
+                _ => {}
+            }
+        }
+    }
+
+    // Assignments of a variable or a field to itself, like `a.b = a.b`
+    fn self_assignment(&mut self, bin: &ASTBinary, range: Range) {
+        if let (Some(target), Some(value)) = (dot_path(&bin.left), dot_path(&bin.right)) {
+            if target == value {
+                self.warn(err!(SELF_ASSIGNMENT, range, &target.join(".")));
+            }
+        }
+    }
+
+    // Expressions which aren't a part of a chain
+    fn expression_part(&mut self, exp: &ASTExpression) {
+        match exp {
+            ASTExpression::Var(var) => self.use_name(&var.value),
+            ASTExpression::Str(_) | ASTExpression::Float(_) | ASTExpression::Int(_) | ASTExpression::Bool(_) | ASTExpression::Char(_) | ASTExpression::None(_) => {},
+            ASTExpression::TempStr(temp) => {
+                for part in &temp.parts {
+                    if let ASTTempStrPart::Interpolation(interpolation) = part { self.expression(&interpolation.value) };
+                }
+            },
+            ASTExpression::Binary(_) | ASTExpression::Unary(_) | ASTExpression::DotAccess(_) | ASTExpression::IndexAccess(_) | ASTExpression::Optional(_) | ASTExpression::Call(_) => self.expression(exp),
+            ASTExpression::ModAccess(access) => self.mod_access(access),
+            ASTExpression::Block(block) => self.block(block),
+            ASTExpression::Function(func) => self.function(func),
+            ASTExpression::Init(init) => {
+                self.mod_access_value(&init.target);
+                if let Some(typings) = &init.typings { self.list_typing(typings) };
+                for (_, value, _) in &init.params.pairs {
+                    if let Some(value) = value { self.expression(value) };
+                }
+            },
+            ASTExpression::Iterator(iter) => {
+                self.expression(&iter.start);
+                self.expression(&iter.end);
+            },
+            ASTExpression::ForIn(for_in) => {
+                self.expression(&for_in.iterable);
+                self.scopes.push(vec![]);
+                self.declare(&for_in.var);
+                self.expression(&for_in.body);
+                self.pop_scope();
+            },
+            ASTExpression::While(while_loop) => {
+                self.expression(&while_loop.condition);
+                self.expression(&while_loop.body);
+            },
+            ASTExpression::If(if_exp) => {
+                self.expression(&if_exp.condition);
+                self.expression(&if_exp.then);
+                if let Some(otherwise) = &if_exp.otherwise { self.expression(otherwise) };
+            },
+            ASTExpression::Declare(decl) => {
+                if let Some(typings) = &decl.typings { self.typing(typings) };
+                if let Some(value) = &decl.value { self.expression(value) };
+                self.pattern(&decl.var);
+            },
+            ASTExpression::Tuple(list) => self.expression_list(list),
+            ASTExpression::Yield(yield_exp) => {
+                if let Some(value) = &yield_exp.value { self.expression(value) };
+            },
+            ASTExpression::Spread(spread) => self.expression(&spread.value),
+            ASTExpression::Match(match_exp) => {
+                self.expression(&match_exp.expression);
+                for arm in &match_exp.arms {
+                    for possibility in &arm.possibilities {
+                        match possibility {
+                            ASTMatchArmExpressions::Enum(access) | ASTMatchArmExpressions::EnumVar(access) => self.mod_access(access),
+                            ASTMatchArmExpressions::Iterator(iter) => {
+                                self.expression(&iter.start);
+                                self.expression(&iter.end);
+                            },
+                            ASTMatchArmExpressions::Tuple(list) => self.expression_list(list),
+                            _ => {}
+                        }
+                    }
+                    if let Some(guard) = &arm.guard { self.expression(guard) };
+                    self.expression(&arm.body);
+                }
+            },
+            ASTExpression::Await(await_exp) => self.expression(&await_exp.expression)
+        }
+    }
+}
+
+// The names of a variable or a chain of field accesses, like `a.b.c`
+fn dot_path(exp: &ASTExpression) -> Option<Vec<&str>> {
+    let mut path: Vec<&str> = vec![];
+    let mut current = exp;
+    while let ASTExpression::DotAccess(access) = current {
+        path.push(&access.target.value);
+        current = &access.value;
+    }
+    match current {
+        ASTExpression::Var(var) => path.push(&var.value),
+        _ => return None
+    }
+    path.reverse();
+    Some(path)
+}
+
+// Runs all lints on the module. The diagnostics are warnings - `LintContext::apply` gives them their real level.
+pub fn check_module(ast: &[ASTStatement], errors: &mut ErrorCollector) {
+    let mut checker = LintChecker { errors, scopes: vec![], refs: HashSet::new() };
+    let mut imports: Vec<(String, Range)> = vec![];
+    // Declarations which aren't exported, and the index of the statement they're in
+    let mut declarations: Vec<(&ASTVar, usize)> = vec![];
+    // The names used by every statement
+    let mut references: Vec<HashSet<String>> = vec![];
+    for (ind, statement) in ast.iter().enumerate() {
+        let mut inner = statement;
+        while let ASTStatement::Meta(meta) = inner {
+            inner = &meta.target;
+        }
+        match inner {
+            ASTStatement::Import(decl) => {
+                if let Some(alias) = &decl.r#as { imports.push((alias.value.clone(), alias.range)) };
+                if let ASTImportThing::Items(items) = &decl.thing {
+                    for item in items {
+                        imports.push((item.r#as.as_ref().map_or_else(|| item.name.clone(), |alias| alias.value.clone()), item.range));
+                    }
+                }
+            },
+            ASTStatement::Struct(decl) => declarations.push((&decl.name, ind)),
+            ASTStatement::EnumDeclaration(decl) => declarations.push((&decl.name, ind)),
+            ASTStatement::Type(decl) => declarations.push((&decl.name, ind)),
+            _ => {}
+        }
+        checker.statement(statement);
+        references.push(std::mem::take(&mut checker.refs));
+    }
+    for (name, range) in imports {
+        if !name.starts_with('_') && !references.iter().any(|refs| refs.contains(&name)) {
+            checker.warn(err!(UNUSED_IMPORT, range, &name));
+        }
+    }
+    for (name, ind) in declarations {
+        // Recursive types don't count as used
+        if !name.value.starts_with('_') && !references.iter().enumerate().any(|(other, refs)| other != ind && refs.contains(&name.value)) {
+            checker.warn(err!(DEAD_CODE, name.range, &name.value));
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
-use crate::{file_host::{FileHost}, symbol::{Symbol, SymbolRef, StatementOrExpression}};
-use parser::{ast::{Parser, model::{ASTImportThing, ASTStatement}}};
+use crate::{file_host::{FileHost}, symbol::{Symbol, SymbolRef, SymbolCollector, StatementOrExpression}, lints::{self, LintContext}};
+use parser::{ast::{Parser, model::{ASTImportThing, ASTStatement, ASTMain}}};
 use errors::*;
 use crate::path::file_dir_and_join;
 
@@ -8,7 +8,14 @@ pub struct Module {
     pub local: HashMap<String, SymbolRef>,
     pub exported: HashMap<String, SymbolRef>,
     pub temporary: HashMap<String, Symbol>,
-    pub filename: String
+    // Impl blocks which aren't checked yet
+    pub impls: Vec<Symbol>,
+    // The main entry, which is checked after the declarations
+    pub main: Option<ASTMain>,
+    pub filename: String,
+    pub file: FileId,
+    // Warnings and notes collected while creating the module
+    pub warnings: Vec<BaseError>
 }
 
 impl Module {
@@ -22,17 +29,41 @@ impl Module {
         let mut temp_syms: HashMap<String, Symbol> = HashMap::new();
         let mut local: HashMap<String, SymbolRef> = HashMap::new();
         let mut exported: HashMap<String, SymbolRef> = HashMap::new();
-        let mut errors = ErrorCollector::new(filename);
+        let mut impls: Vec<Symbol> = Vec::new();
+        let mut main: Option<ASTMain> = None;
+        let file = host.source_map_mut().add(filename, content.to_string());
+        let mut errors = ErrorCollector::new(file);
         let mut parser =  Parser::new(&content, &mut errors);
+        parser.max_depth = host.max_depth();
         let ast = parser.parse();
-        for statement in ast {
+        let lint_ctx = LintContext::new(host.lint_config(), &ast, &mut errors);
+        // Syntax errors leave holes in the AST, which lead to false positives
+        if !errors.has_errors() { lints::check_module(&ast, &mut errors) };
+        lint_ctx.apply(&mut errors);
+        for mut statement in ast {
+            // Lint attributes are already collected, the statement is declared like any other
+            while let ASTStatement::Meta(meta) = statement {
+                statement = *meta.target;
+            }
             if let Some((name, range, is_exported, decl)) = match statement {
                 ASTStatement::Import(decl) => {
-                    let path_to_mod = file_dir_and_join(filename, &decl.path.value);
-                    let module = if let Some(m) = host.get_or_create(&path_to_mod)? { m } else {
-                        errors.push(err!(MOD_NOT_FOUND, decl.path.range, &filename, &decl.path.value));
+                    let path_to_mod = host.normalize_path(&file_dir_and_join(filename, &decl.path.value));
+                    if let Some(start) = host.import_stack().iter().position(|(file, _)| *file == path_to_mod) {
+                        let mut err = err!(FILE_RECURSION, decl.path.range, filename, &path_to_mod);
+                        for (_, range) in &host.import_stack()[start..] {
+                            err = err.with_label("Imported here", *range);
+                        }
+                        errors.push(err);
+                        continue;
+                    }
+                    host.import_stack().push((filename.to_string(), decl.path.range));
+                    let found = host.get_or_create(&path_to_mod).map(|module| module.is_some());
+                    host.import_stack().pop();
+                    if !found? {
+                        errors.push(err!(MOD_NOT_FOUND, decl.path.range, &decl.path.value));
                         continue;
                     };
+                    let module = host.get(&path_to_mod).unwrap();
                     match decl.thing {
                         ASTImportThing::All => {
                             for (name, id) in module.exported.iter() {
@@ -43,7 +74,7 @@ impl Module {
                             for item in item_list {
                                 let item_name = item.name.clone();
                                 let item_id = if let Some(id) = module.exported.get(&item_name) { id.clone() } else {
-                                    errors.push(err!(TYPE_NOT_FOUND_FROM_MOD, item.range, &filename, &item_name, &decl.path.to_string()));
+                                    errors.push(err!(TYPE_NOT_FOUND_FROM_MOD, item.range, &item_name, &decl.path.value));
                                     SymbolRef::new_ref(0)
                                 };
                                 let name = if let Some(alias) = item.r#as {
@@ -60,18 +91,46 @@ impl Module {
                 ASTStatement::EnumDeclaration(decl) => Some((decl.name.value.clone(), decl.name.range, false, StatementOrExpression::EnumStatement(decl))),
                 ASTStatement::Struct(decl) => Some((decl.name.value.clone(), decl.name.range, false, StatementOrExpression::StructStatement(decl))),
                 ASTStatement::Type(decl) => Some((decl.name.value.clone(), decl.name.range, false, StatementOrExpression::TypeStatement(decl))),
+                ASTStatement::Static(decl) => Some((decl.var.value.clone(), decl.var.range, false, StatementOrExpression::StaticStatement(decl))),
+                ASTStatement::Main(decl) => {
+                    main = Some(decl);
+                    None
+                },
+                ASTStatement::Impl(decl) => {
+                    impls.push(Symbol::empty(host.get_unique_id(), format!("impl {} for {}", decl.partial, decl.target), StatementOrExpression::ImplStatement(Box::new(decl))));
+                    None
+                },
                 ASTStatement::Export(decl) => {
                     match *decl.value {
                         ASTStatement::EnumDeclaration(decl) => Some((decl.name.value.clone(), decl.name.range, true, StatementOrExpression::EnumStatement(decl))),
                         ASTStatement::Struct(decl) => Some((decl.name.value.clone(), decl.name.range, true, StatementOrExpression::StructStatement(decl))),
                         ASTStatement::Type(decl) => Some((decl.name.value.clone(), decl.name.range, true, StatementOrExpression::TypeStatement(decl))),
+                        ASTStatement::Static(decl) => Some((decl.var.value.clone(), decl.var.range, true, StatementOrExpression::StaticStatement(decl))),
+                        // Implementations apply everywhere, exporting them doesn't change anything
+                        ASTStatement::Impl(decl) => {
+                            impls.push(Symbol::empty(host.get_unique_id(), format!("impl {} for {}", decl.partial, decl.target), StatementOrExpression::ImplStatement(Box::new(decl))));
+                            None
+                        },
                         _ => None
                     }
                 }
                 _ => None
             } {
-                if temp_syms.contains_key(&name) || local.contains_key(&name) {
-                    errors.push(err!(DUPLICATE_IDENT, range, &name));
+                if let Some(sym) = temp_syms.get(&name) {
+                    let mut err = err!(DUPLICATE_IDENT, range, &name);
+                    if let Some(first_range) = sym.declaration.name_range() {
+                        err = err.with_label("First defined here", first_range);
+                    }
+                    errors.push(err);
+                    continue;
+                }
+                if local.contains_key(&name) {
+                    let mut err = err!(DUPLICATE_IDENT, range, &name);
+                    // Point to the definition in the module the name was imported from
+                    if let Some(first_range) = host.checker().get_symbol(&local[&name].id).and_then(|sym| sym.declaration.name_range()) {
+                        err = err.with_label("First defined here", first_range);
+                    }
+                    errors.push(err);
                     continue;
                 }
                 let id = host.get_unique_id();
@@ -81,10 +140,16 @@ impl Module {
                 temp_syms.insert(name.to_string(), Symbol::empty(id, name, decl));
             }
         }
-        if errors.collected.is_empty() {
-            Ok(Self { local, exported, filename: filename.to_string(), temporary: temp_syms })
-        } else {
+        if errors.has_errors() {
+            return Err(errors);
+        }
+        let mut module = Self { local, exported, filename: filename.to_string(), file, temporary: temp_syms, impls, main, warnings: vec![] };
+        host.checker().check_module(&mut module, &mut errors);
+        if errors.has_errors() {
             Err(errors)
+        } else {
+            module.warnings = errors.collected;
+            Ok(module)
         }
     }
 
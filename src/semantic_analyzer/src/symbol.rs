@@ -7,6 +7,9 @@ bitflags::bitflags! {
         const CONST = 1 << 1;
         const OPTIONAL = 1 << 2;
         const TYPE_PARAM = 1 << 3;
+        // Parameters which can be left out of a call
+        const DEFAULT = 1 << 4;
+        const SPREAD = 1 << 5;
     }
 }
 
@@ -20,26 +23,60 @@ pub enum StatementOrExpression {
     EnumStatement(ASTEnumDeclaration),
     StructStatement(ASTStruct),
     TypeStatement(ASTType),
+    ImplStatement(Box<ASTImpl>),
+    StaticStatement(Box<ASTStatic>),
     None
 }
 
+impl StatementOrExpression {
+    pub fn name_range(&self) -> Option<Range> {
+        match self {
+            Self::EnumStatement(decl) => Some(decl.name.range),
+            Self::StructStatement(decl) => Some(decl.name.range),
+            Self::TypeStatement(decl) => Some(decl.name.range),
+            Self::ImplStatement(decl) => Some(decl.target.range()),
+            Self::StaticStatement(decl) => Some(decl.var.range),
+            Self::None => None
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct SymbolProperty {
-    kind: SymbolRef,
-    flags: ASTModifiers
+    pub kind: SymbolRef,
+    pub flags: ASTModifiers
 }
 
+#[derive(Clone)]
 pub enum SymbolKind {
     Struct(HashMap<String, SymbolProperty>),
     Enum(HashMap<String, SymbolRef>),
     Fn{
-        parameters: HashMap<String, SymbolRef>,
+        // In the order they're declared in
+        parameters: Vec<(String, SymbolRef)>,
         return_type: SymbolRef
     },
+    Tuple(Vec<SymbolRef>),
     Module(HashMap<String, SymbolRef>),
+    // The constraint of the type parameter, if it has one
+    TypeParam(Option<SymbolRef>),
+    // The aliased type
+    Alias(SymbolRef),
+    // The type of a static value
+    Static(SymbolRef),
+    Primitive,
     None
 }
 
 impl SymbolKind {
+
+    pub fn get_property(&self, name: &str) -> Option<&SymbolProperty> {
+        match self {
+            Self::Struct(props) => props.get(name),
+            _ => None
+        }
+    }
+
     pub fn is_enum(&self) -> bool {
         matches!(self, Self::Enum(_))
     }
@@ -57,7 +94,7 @@ impl SymbolKind {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct SymbolRef {
     pub id: u32,
     pub instance_id: Option<usize>,
@@ -102,11 +139,24 @@ impl SymbolRef {
         self.flags.contains(SymbolFlags::TYPE_PARAM)
     }
 
+    pub fn has_default(&self) -> bool {
+        self.flags.contains(SymbolFlags::DEFAULT)
+    }
+
+    pub fn is_spread(&self) -> bool {
+        self.flags.contains(SymbolFlags::SPREAD)
+    }
+
     pub fn make_optional(mut self) -> Self {
         self.flags.set(SymbolFlags::OPTIONAL, true);
         self
     }
 
+    pub fn make_required(mut self) -> Self {
+        self.flags.set(SymbolFlags::OPTIONAL, false);
+        self
+    }
+
     pub fn make_const(mut self) -> Self {
         self.flags.set(SymbolFlags::CONST, true);
         self
@@ -121,6 +171,16 @@ impl SymbolRef {
         self.flags.set(SymbolFlags::TYPE_PARAM, true);
         self
     }
+
+    pub fn make_default(mut self) -> Self {
+        self.flags.set(SymbolFlags::DEFAULT, true);
+        self
+    }
+
+    pub fn make_spread(mut self) -> Self {
+        self.flags.set(SymbolFlags::SPREAD, true);
+        self
+    }
     
 }
 
@@ -144,7 +204,8 @@ pub struct Symbol {
     pub name: String,
     pub id: u32,
     pub kind: SymbolKind,
-    pub type_params: HashMap<String, Option<SymbolRef>>,
+    // In the order they're declared in
+    pub type_params: Vec<SymbolRef>,
     pub instances: Vec<SymbolInstance>,
     pub declaration: StatementOrExpression,
     pub impls: Vec<SymbolRef>
@@ -163,30 +224,25 @@ impl Symbol {
             id,
             name,
             kind: SymbolKind::None,
-            type_params: HashMap::new(),
+            type_params: Vec::new(),
             instances: Vec::new(),
             declaration: decl,
             impls: Vec::new()
         }
     }
 
-    pub fn create_or_get_instance(&mut self, params: Vec<SymbolRef>, ast: &ASTListTyping) -> LazyResult<SymbolRef> {
+    pub fn create_or_get_instance(&mut self, params: Vec<SymbolRef>, range: Range) -> LazyResult<SymbolRef> {
         let params_len = params.len();
         if params_len != self.type_params.len() { 
-            return Err(err!(INVALID_AMOUNT_OF_TYPE_PARAMS, ast.range, &self.type_params.len().to_string(), &params_len.to_string()));
+            return Err(err!(INVALID_AMOUNT_OF_TYPE_PARAMS, range, &self.type_params.len().to_string(), &params_len.to_string()));
         };
-        'outer: for instance in &self.instances {
-            for ind in 0..params_len {
-                if params[ind].id != instance.type_args[ind].id {
-                    continue 'outer;
-                }
-            }
+        if let Some(instance) = self.instances.iter().find(|instance| instance.type_args == params) {
             return Ok(SymbolRef::new_instance(self.id, instance.id));
         };
-        let instance_id = self.instances.len() + 1;
+        let instance_id = self.instances.len();
         self.instances.push(SymbolInstance {
             id: instance_id,
-            // Create a new kind from this symbol's kind, but replace type arguments with actual values
+            // Filled in by the type checker, which replaces the type parameters with the type arguments
             kind: SymbolKind::None,
             type_args: params
         });
@@ -211,10 +267,10 @@ impl Symbol {
         };
         for implementation in &self.impls {
             if let SymbolKind::Struct(properties) = implementation.get_kind(collector) {
-                let prop = properties.get(name)?;
-                if prop.flags.contains(ASTModifiers::STATIC) {
-                    return Some(&prop.kind);
-                };
+                match properties.get(name) {
+                    Some(prop) if prop.flags.contains(ASTModifiers::STATIC) => return Some(&prop.kind),
+                    _ => {}
+                }
             }
         };
         None
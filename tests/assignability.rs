@@ -0,0 +1,53 @@
+mod common;
+use common::error_codes_with;
+
+const DECLARATIONS: &str = "
+type WithName = { name: str }
+type WithAge = { age: i8 }
+
+struct Human {
+    name: str,
+    age: i8
+}
+
+struct Rock {
+    weight: i32
+}
+
+struct Robot {
+    name: i32
+}
+";
+
+// The codes of the errors in a struct with the field
+fn check_field(field: &str) -> Vec<Option<u16>> {
+    error_codes_with(DECLARATIONS, &format!("struct Test {{ {} }}", field))
+}
+
+#[test]
+fn structs_are_assignable_to_partials() {
+    assert_eq!(check_field("a: WithName = new Human { name: \"Google\", age: 19 }"), vec![]);
+    assert_eq!(check_field("a: WithName + WithAge = new Human { name: \"Google\", age: 19 }"), vec![]);
+    assert_eq!(check_field("a: WithName = new Rock { weight: 5 }"), vec![Some(2016)]);
+    assert_eq!(check_field("a: WithName = new Robot { name: 5 }"), vec![Some(2017)]);
+    // Partials don't make structs interchangeable
+    assert_eq!(check_field("a: Human = new Robot { name: 5 }"), vec![Some(2011)]);
+}
+
+#[test]
+fn optionals_and_tuples() {
+    assert_eq!(check_field("a: WithName? = none"), vec![]);
+    assert_eq!(check_field("a: WithName = none"), vec![Some(2011)]);
+    assert_eq!(check_field("a: [u8, str] = [1, \"a\"]"), vec![]);
+    assert_eq!(check_field("a: [u8, str] = [1, 2]"), vec![Some(2011)]);
+}
+
+#[test]
+fn function_parameters_are_contravariant() {
+    assert_eq!(check_field("a: (h: Human) = fn(h: WithName) -> i32 1"), vec![]);
+    assert_eq!(check_field("a: (h: WithName) = fn(h: Human) -> i32 1"), vec![Some(2011)]);
+    // Parameters which aren't passed have to be optional
+    assert_eq!(check_field("a: (n: i32) = fn(n: i32, s: str?) -> i32 n"), vec![]);
+    assert_eq!(check_field("a: (n: i32) = fn(n: i32, s: str) -> i32 n"), vec![Some(2011)]);
+    assert_eq!(check_field("a: (n: i32) -> str = fn(n: i32) -> i32 n"), vec![Some(2011)]);
+}
@@ -0,0 +1,125 @@
+use lazy::errors::*;
+
+fn range(file: u32, start: usize, end: usize) -> Range {
+    let file = FileId(file);
+    LoC { line: 1, col: start, pos: start, file }.end(&LoC { line: 1, col: end, pos: end, file })
+}
+
+fn warning(err: BaseError) -> BaseError {
+    err.with_severity(Severity::Warning)
+}
+
+fn collect(errors: Vec<BaseError>) -> ErrorCollector {
+    let mut collector = ErrorCollector::new(FileId(1));
+    collector.collected = errors;
+    collector
+}
+
+fn codes_and_starts(collector: &ErrorCollector) -> Vec<(Option<u16>, u32, usize)> {
+    collector.collected.iter().map(|err| (err.code, err.range.file().0, err.range.start.pos)).collect()
+}
+
+#[test]
+fn normalize_sorts_by_file_and_position() {
+    let mut errors = collect(vec![
+        err!(NAME_NOT_FOUND, range(2, 0, 1), "a"),
+        err!(NAME_NOT_FOUND, range(1, 10, 11), "b"),
+        err!(NAME_NOT_FOUND, range(1, 2, 5), "c"),
+        err!(NAME_NOT_FOUND, range(1, 2, 3), "d")
+    ]);
+    errors.normalize();
+    assert_eq!(errors.collected.iter().map(|err| err.msg.clone()).collect::<Vec<String>>(), vec![
+        messages::NAME_NOT_FOUND("d"), messages::NAME_NOT_FOUND("c"), messages::NAME_NOT_FOUND("b"), messages::NAME_NOT_FOUND("a")
+    ]);
+}
+
+#[test]
+fn normalize_removes_duplicates() {
+    let mut errors = collect(vec![
+        err!(NAME_NOT_FOUND, range(1, 0, 1), "a"),
+        err!(NAME_NOT_FOUND, range(1, 0, 1), "a"),
+        err!(MISMATCHED_TYPES, range(1, 0, 1), "i32", "str"),
+        err!(NAME_NOT_FOUND, range(2, 0, 1), "a"),
+        err!(NAME_NOT_FOUND, range(1, 0, 2), "a")
+    ]);
+    errors.normalize();
+    assert_eq!(codes_and_starts(&errors), vec![(Some(2005), 1, 0), (Some(2011), 1, 0), (Some(2005), 1, 0), (Some(2005), 2, 0)]);
+}
+
+#[test]
+fn normalize_removes_errors_caused_by_syntax_errors() {
+    let mut errors = collect(vec![
+        err!(NAME_NOT_FOUND, range(1, 2, 3), "a"),
+        err!(SEMICOLON, range(1, 0, 5)),
+        err!(NAME_NOT_FOUND, range(1, 5, 6), "b"),
+        // Empty ranges contain the character after them
+        err!(SEMICOLON, range(1, 10, 10)),
+        err!(NAME_NOT_FOUND, range(1, 10, 12), "c"),
+        err!(NAME_NOT_FOUND, range(2, 3, 4), "d"),
+        // Only syntax errors hide the errors after them
+        err!(NAME_NOT_FOUND, range(1, 20, 30), "e"),
+        err!(NAME_NOT_FOUND, range(1, 21, 22), "f"),
+        warning(err!(SEMICOLON, range(1, 40, 50))),
+        err!(NAME_NOT_FOUND, range(1, 41, 42), "g")
+    ]);
+    errors.normalize();
+    assert_eq!(codes_and_starts(&errors), vec![
+        (Some(1007), 1, 0), (Some(2005), 1, 5), (Some(1007), 1, 10), (Some(2005), 1, 20), (Some(2005), 1, 21), (Some(1007), 1, 40), (Some(2005), 1, 41), (Some(2005), 2, 3)
+    ]);
+}
+
+#[test]
+fn normalize_keeps_root_causes() {
+    let fixable = err!(NAME_NOT_FOUND, range(1, 6, 7), "b").with_suggestion("Rename it", range(1, 6, 7), "a", Applicability::MachineApplicable);
+    let maybe = err!(NAME_NOT_FOUND, range(1, 7, 8), "c").with_suggestion("Rename it", range(1, 7, 8), "a", Applicability::MaybeIncorrect);
+    let mut errors = collect(vec![
+        err!(SEMICOLON, range(1, 0, 10)),
+        err!(CONFUSABLE, range(1, 3, 4), ";", ";"),
+        err!(INVALID_CHAR, range(1, 4, 5), "#"),
+        fixable,
+        maybe
+    ]);
+    errors.normalize();
+    assert_eq!(codes_and_starts(&errors), vec![(Some(1007), 1, 0), (Some(1022), 1, 3), (Some(1004), 1, 4), (Some(2005), 1, 6)]);
+}
+
+#[test]
+fn truncating_errors() {
+    let mut errors = collect(vec![
+        err!(NAME_NOT_FOUND, range(1, 0, 1), "a"),
+        warning(err!(NAME_NOT_FOUND, range(1, 1, 2), "b")),
+        err!(NAME_NOT_FOUND, range(1, 2, 3), "c"),
+        warning(err!(NAME_NOT_FOUND, range(1, 3, 4), "d")),
+        err!(NAME_NOT_FOUND, range(1, 4, 5), "e")
+    ]);
+    errors.truncate_errors(5);
+    assert_eq!((errors.collected.len(), errors.hidden), (5, 0));
+    // Warnings after the last kept error are removed too
+    errors.truncate_errors(2);
+    assert_eq!(codes_and_starts(&errors).iter().map(|(_, _, start)| *start).collect::<Vec<usize>>(), vec![0, 1, 2]);
+    assert_eq!(errors.hidden, 1);
+    errors.truncate_errors(2);
+    assert_eq!(errors.hidden, 1);
+    errors.truncate_errors(0);
+    assert_eq!((errors.collected.len(), errors.hidden), (0, 3));
+}
+
+#[test]
+fn summaries() {
+    assert_eq!(collect(vec![]).summary(), None);
+    assert_eq!(collect(vec![err!(SEMICOLON, range(1, 0, 1))]).summary(), Some(String::from("Found 1 error.")));
+    assert_eq!(collect(vec![warning(err!(SEMICOLON, range(1, 0, 1))), warning(err!(SEMICOLON, range(1, 0, 1)))]).summary(), Some(String::from("Found 2 warnings.")));
+    let mixed = collect(vec![
+        err!(SEMICOLON, range(1, 0, 1)),
+        warning(err!(SEMICOLON, range(1, 0, 1))),
+        err!(SEMICOLON, range(1, 0, 1)).with_severity(Severity::Note),
+        err!(SEMICOLON, range(1, 0, 1))
+    ]);
+    assert_eq!(mixed.summary(), Some(String::from("Found 2 errors, 1 warning and 1 note.")));
+    let mut truncated = collect(vec![err!(SEMICOLON, range(1, 0, 1)), err!(SEMICOLON, range(1, 1, 2)), err!(SEMICOLON, range(1, 2, 3))]);
+    truncated.truncate_errors(1);
+    assert_eq!(truncated.summary(), Some(String::from("Found 3 errors. 2 errors are not shown because of the error limit.")));
+    let mut truncated = collect(vec![err!(SEMICOLON, range(1, 0, 1)), err!(SEMICOLON, range(1, 1, 2))]);
+    truncated.truncate_errors(1);
+    assert_eq!(truncated.summary(), Some(String::from("Found 2 errors. 1 error is not shown because of the error limit.")));
+}
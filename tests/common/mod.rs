@@ -0,0 +1,52 @@
+// Helpers shared by the tests which check whole modules. Not every test file uses all of them.
+#![allow(dead_code)]
+
+use lazy::semantic_analyzer::file_host::VirtualFileHost;
+use lazy::errors::{BaseError, ErrorLabelVariants};
+
+// Checks the source as the "main" module, and returns all of its diagnostics, including warnings
+pub fn diagnostics_with(host: &mut VirtualFileHost, source: &str) -> Vec<BaseError> {
+    match host.create_virtual("main", source.to_string()) {
+        Ok(module) => module.map_or_else(Vec::new, |module| module.warnings.clone()),
+        Err(errors) => errors.collected
+    }
+}
+
+// Checks the source as the "main" module, and panics if there are any errors
+pub fn checked(source: &str) -> VirtualFileHost {
+    let mut host = VirtualFileHost::new();
+    if let Err(errors) = host.create_virtual("main", source.to_string()) {
+        panic!("{:?}", errors.collected);
+    }
+    host
+}
+
+pub fn diagnostics(source: &str) -> Vec<BaseError> {
+    diagnostics_with(&mut VirtualFileHost::new(), source)
+}
+
+// The codes of the errors in the module, warnings are left out
+pub fn error_codes(source: &str) -> Vec<Option<u16>> {
+    diagnostics(source).iter().filter(|err| err.is_error()).map(|err| err.code).collect()
+}
+
+// The codes of the errors in the source, with the declarations added before it
+pub fn error_codes_with(declarations: &str, source: &str) -> Vec<Option<u16>> {
+    error_codes(&format!("{}\n{}", declarations, source))
+}
+
+// The codes of the errors in a main block with the body, with the declarations added before it
+pub fn main_errors(declarations: &str, body: &str) -> Vec<Option<u16>> {
+    error_codes_with(declarations, &format!("main {{ {} }}", body))
+}
+
+// The message, file and highlighted text of every label which points to a range
+pub fn labels(host: &VirtualFileHost, err: &BaseError) -> Vec<(String, String, String)> {
+    err.labels.iter().filter_map(|label| match &label.variant {
+        ErrorLabelVariants::Sub(range) => {
+            let file = host.sources.get(range.file()).expect("the label should point to a known file");
+            Some((label.msg.clone(), file.name.clone(), file.content[range.start.pos..range.end.pos].to_string()))
+        },
+        _ => None
+    }).collect()
+}
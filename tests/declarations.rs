@@ -0,0 +1,83 @@
+mod common;
+use common::{checked, diagnostics, error_codes, main_errors};
+use lazy::semantic_analyzer::symbol::{SymbolKind, ASTModifiers};
+
+// The type names and modifiers of the struct's fields
+fn fields(source: &str, name: &str) -> Vec<(String, String, ASTModifiers)> {
+    let host = checked(source);
+    let reference = host.files["main"].get_sym(name).unwrap().clone();
+    match reference.get_kind(&host.checker) {
+        SymbolKind::Struct(props) => {
+            let mut fields = props.iter().map(|(name, prop)| (name.clone(), host.checker.type_name(&prop.kind), prop.flags)).collect::<Vec<(String, String, ASTModifiers)>>();
+            fields.sort_by(|a, b| a.0.cmp(&b.0));
+            fields
+        },
+        _ => panic!("{} is not a struct", name)
+    }
+}
+
+#[test]
+fn struct_field_types_are_inferred_from_defaults() {
+    assert_eq!(fields("struct A { a: i32, b = 1.5, c = \"c\", d: str? = none }", "A"), vec![
+        (String::from("a"), String::from("i32"), ASTModifiers::empty()),
+        (String::from("b"), String::from("f32"), ASTModifiers::empty()),
+        (String::from("c"), String::from("str"), ASTModifiers::empty()),
+        (String::from("d"), String::from("str?"), ASTModifiers::empty())
+    ]);
+    assert_eq!(error_codes("struct A { a }"), vec![Some(2009)]);
+    assert_eq!(error_codes("struct A { a: i32 = \"a\" }"), vec![Some(2011)]);
+}
+
+#[test]
+fn struct_field_modifiers() {
+    let fields = fields("struct A { const a: i32 = 1, static b = 2, private c: str }", "A");
+    assert_eq!(fields.iter().map(|(_, _, flags)| *flags).collect::<Vec<ASTModifiers>>(), vec![ASTModifiers::CONST, ASTModifiers::STATIC, ASTModifiers::PRIVATE]);
+}
+
+#[test]
+fn duplicate_fields() {
+    assert_eq!(error_codes("struct A { a: i32, b: str, a: str }"), vec![Some(2008)]);
+    assert_eq!(error_codes("struct A { a: i32, b: str }"), vec![]);
+}
+
+#[test]
+fn duplicate_variants() {
+    assert_eq!(error_codes("enum A { B, C: i32, B: str }"), vec![Some(2010)]);
+    assert_eq!(error_codes("enum A { B, C: i32 }"), vec![]);
+}
+
+#[test]
+fn variant_payloads() {
+    let declarations = "enum Shape { Circle: f32, Rect: [f32, f32], Empty }\nenum Option<T> { Some: T, None }\n";
+    assert_eq!(main_errors(declarations, "let a = Shape::Circle(1.5); let b = Shape::Rect([1.5, 2]); let c = Shape::Empty;"), vec![]);
+    assert_eq!(main_errors(declarations, "let a = Shape::Circle(\"a\");"), vec![Some(2011)]);
+    assert_eq!(main_errors(declarations, "let a = Shape::Rect([1.5]);"), vec![Some(2011)]);
+    assert_eq!(main_errors(declarations, "let a = Shape::Rect([1.5, 2], [1.5, 2]);"), vec![Some(2012)]);
+    assert_eq!(main_errors(declarations, "let a = Shape::Empty(1);"), vec![Some(2012)]);
+    assert_eq!(main_errors(declarations, "let a = Shape::Square(1.5);"), vec![Some(2005)]);
+    assert_eq!(main_errors(declarations, "let a = Option::Some<str>(\"a\");"), vec![]);
+    assert_eq!(main_errors(declarations, "let a = Option::Some<str>(1);"), vec![Some(2011)]);
+    assert_eq!(error_codes("enum A { B: C }"), vec![Some(2005)]);
+}
+
+#[test]
+fn aliases_are_transparent() {
+    let declarations = "type Num = i32\ntype Pair<T> = [T, T]\ntype Named = { name: str }\nstruct Human { name: str, age: Num }\n";
+    assert_eq!(main_errors(declarations, "let a: Num = 1; let b: i32 = a; let c: Pair<Num> = [a, b];"), vec![]);
+    assert_eq!(main_errors(declarations, "let a: Num = \"a\";"), vec![Some(2011)]);
+    assert_eq!(main_errors(declarations, "let a: Pair<str> = [\"a\", 1];"), vec![Some(2011)]);
+    assert_eq!(main_errors(declarations, "let a: Named = new Human { name: \"a\", age: 1 }; let b: str = a.name;"), vec![]);
+}
+
+#[test]
+fn alias_cycles() {
+    let errors = diagnostics("type A = B\ntype B = A");
+    assert_eq!(errors.iter().map(|err| err.code).collect::<Vec<Option<u16>>>(), vec![Some(2013)]);
+    assert!(errors[0].msg.contains("A -> B -> A"), "{}", errors[0].msg);
+    let errors = diagnostics("type A = [B, i32]\ntype B = [C]\ntype C = { a: A }");
+    assert_eq!(errors.iter().filter(|err| err.is_error()).map(|err| err.code).collect::<Vec<Option<u16>>>(), vec![Some(2013)]);
+    assert!(errors[0].msg.contains("A -> B -> C -> A"), "{}", errors[0].msg);
+    assert_eq!(error_codes("type A = A"), vec![Some(2013)]);
+    // Going through a struct isn't a cycle, because structs are nominal
+    assert_eq!(error_codes("type A = { b: B? }\nstruct B { a: A }"), vec![]);
+}
@@ -0,0 +1,21 @@
+use lazy::errors::{diagnostics::{Diagnostics, parse_code, format_code}, explanations::get_explanation};
+
+#[test]
+fn every_diagnostic_has_an_explanation() {
+    for diagnostic in Diagnostics::ALL {
+        assert!(get_explanation(diagnostic.code).is_some(), "{} doesn't have an explanation.", format_code(diagnostic.code));
+    }
+}
+
+#[test]
+fn parsing_codes() {
+    assert_eq!(parse_code("E1009"), Some(1009));
+    assert_eq!(parse_code("e1009"), Some(1009));
+    assert_eq!(parse_code("1009"), Some(1009));
+    assert_eq!(parse_code(&format_code(7)), Some(7));
+    assert_eq!(parse_code("EE1009"), None);
+    assert_eq!(parse_code("eE1009"), None);
+    assert_eq!(parse_code("+1009"), None);
+    assert_eq!(parse_code("E+1009"), None);
+    assert_eq!(parse_code("E"), None);
+}
@@ -0,0 +1,136 @@
+use lazy::errors::*;
+use lazy::errors::fix::{apply_suggestions, apply_machine_applicable, apply_until_fixed, MAX_FIX_PASSES};
+use lazy::semantic_analyzer::file_host::VirtualFileHost;
+use std::collections::HashMap;
+use std::cell::RefCell;
+
+fn range(file: FileId, start: usize, end: usize) -> Range {
+    LoC { line: 1, col: start, pos: start, file }.end(&LoC { line: 1, col: end, pos: end, file })
+}
+
+fn suggestion(file: FileId, start: usize, end: usize, replacement: &str) -> BaseError {
+    err!(UNEXPECTED_EOF, range(file, start, end)).with_suggestion("Replace it", range(file, start, end), replacement, Applicability::MachineApplicable)
+}
+
+// Suggests replacing every occurrence of `pattern`, including overlapping ones
+fn replace_all(map: &SourceMap, file: FileId, pattern: &str, replacement: &str) -> ErrorCollector {
+    let chars: Vec<char> = map.get_content(file).unwrap().chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    let mut errors = ErrorCollector::new(file);
+    for start in 0..chars.len() {
+        if chars[start..].starts_with(&pattern) {
+            errors.push(suggestion(file, start, start + pattern.len(), replacement));
+        }
+    }
+    errors
+}
+
+#[test]
+fn applying_suggestions() {
+    let file = FileId::default();
+    let errors = [suggestion(file, 4, 5, "b"), suggestion(file, 0, 3, "const"), suggestion(file, 10, 10, ";")];
+    let suggestions = errors.iter().flat_map(|err| err.suggestions()).collect::<Vec<&ErrorSuggestion>>();
+    assert_eq!(apply_suggestions("let a = 😀1", &suggestions), (String::from("const b = 😀1;"), 3));
+    // Overlapping suggestions are skipped, the one which starts first wins
+    let errors = [suggestion(file, 2, 6, "x"), suggestion(file, 0, 3, "y"), suggestion(file, 3, 4, "z"), suggestion(file, 5, 20, "w")];
+    let suggestions = errors.iter().flat_map(|err| err.suggestions()).collect::<Vec<&ErrorSuggestion>>();
+    assert_eq!(apply_suggestions("abcdefg", &suggestions), (String::from("yzefg"), 2));
+}
+
+#[test]
+fn only_machine_applicable_suggestions_of_the_file() {
+    let mut map = SourceMap::new();
+    let main = map.add("main.lazy", String::from("abc"));
+    let other = map.add("other.lazy", String::from("abc"));
+    let errors = vec![
+        suggestion(main, 0, 1, "x"),
+        suggestion(other, 1, 2, "y"),
+        err!(UNEXPECTED_EOF, range(main, 2, 3)).with_suggestion("Maybe", range(main, 2, 3), "z", Applicability::MaybeIncorrect)
+    ];
+    assert_eq!(apply_machine_applicable("abc", main, &errors), (String::from("xbc"), 1));
+    assert_eq!(apply_machine_applicable("abc", other, &errors), (String::from("ayc"), 1));
+}
+
+#[test]
+fn fixing_until_there_are_no_suggestions() {
+    let files = RefCell::new(HashMap::from([(String::from("main.lazy"), String::from("aaaa aaa"))]));
+    let mut passes = 0;
+    let total = apply_until_fixed(|| {
+        passes += 1;
+        let mut map = SourceMap::new();
+        let file = map.add("main.lazy", files.borrow()["main.lazy"].clone());
+        let errors = replace_all(&map, file, "aa", "a");
+        (map, errors)
+    }, |name, content| {
+        files.borrow_mut().insert(name.to_string(), content);
+        Ok::<(), ()>(())
+    });
+    // aaaa aaa -> aa aa -> a a, the last pass doesn't find anything to fix
+    assert_eq!(total, Ok(5));
+    assert_eq!(passes, 3);
+    assert_eq!(files.borrow()["main.lazy"], "a a");
+}
+
+#[test]
+fn fixing_stops_after_the_maximum_amount_of_passes() {
+    let mut passes = 0;
+    let total = apply_until_fixed(|| {
+        passes += 1;
+        let mut map = SourceMap::new();
+        let file = map.add("main.lazy", String::from("a"));
+        let errors = replace_all(&map, file, "a", "a");
+        (map, errors)
+    }, |_, _| Ok::<(), ()>(()));
+    assert_eq!((total, passes), (Ok(MAX_FIX_PASSES), MAX_FIX_PASSES));
+    let total = apply_until_fixed(|| {
+        let mut map = SourceMap::new();
+        let file = map.add("main.lazy", String::from("a"));
+        let errors = replace_all(&map, file, "a", "b");
+        (map, errors)
+    }, |name, _| Err(name.to_string()));
+    assert_eq!(total, Err(String::from("main.lazy")));
+}
+
+#[test]
+fn fixing_a_module() {
+    let content = RefCell::new(String::from("type A = i32;\nmain {\n    let a: A = 1;\n    print(`${a} ${`inner`}`);\n    print(`done`);\n}"));
+    let total = apply_until_fixed(|| {
+        let mut host = VirtualFileHost::new();
+        let errors = match host.create_virtual("main", content.borrow().clone()) {
+            Ok(module) => {
+                let module = module.unwrap();
+                let mut warnings = ErrorCollector::new(module.file);
+                warnings.collected = module.warnings.clone();
+                warnings
+            },
+            Err(errors) => errors
+        };
+        (host, errors)
+    }, |name, fixed| {
+        assert_eq!(name, "main");
+        *content.borrow_mut() = fixed;
+        Ok::<(), ()>(())
+    });
+    assert_eq!(total, Ok(2));
+    assert_eq!(*content.borrow(), "type A = i32;\nmain {\n    let a: A = 1;\n    print(`${a} ${\"inner\"}`);\n    print(\"done\");\n}");
+}
+
+#[test]
+fn fixing_confusables() {
+    let content = RefCell::new(String::from("main {\n    let a = 1\u{037E}\n    a + 1;\n}"));
+    let total = apply_until_fixed(|| {
+        let mut host = VirtualFileHost::new();
+        let mut errors = match host.create_virtual("main", content.borrow().clone()) {
+            Ok(module) => ErrorCollector::new(module.unwrap().file),
+            Err(errors) => errors
+        };
+        // Like the CLI, the diagnostics are normalized before they're fixed
+        errors.normalize();
+        (host, errors)
+    }, |_, fixed| {
+        *content.borrow_mut() = fixed;
+        Ok::<(), ()>(())
+    });
+    assert_eq!(total, Ok(1));
+    assert_eq!(*content.borrow(), "main {\n    let a = 1;\n    a + 1;\n}");
+}
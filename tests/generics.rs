@@ -0,0 +1,201 @@
+mod common;
+use common::{checked, error_codes, main_errors};
+use lazy::semantic_analyzer::{file_host::VirtualFileHost, symbol::{SymbolKind, SymbolRef, ToSymbol}};
+
+fn get(host: &VirtualFileHost, name: &str) -> SymbolRef {
+    host.files["main"].get_sym(name).unwrap().clone()
+}
+
+fn field(host: &VirtualFileHost, reference: &SymbolRef, name: &str) -> SymbolRef {
+    match reference.get_kind(&host.checker) {
+        SymbolKind::Struct(props) => props[name].kind.clone(),
+        _ => panic!("{} is not a struct", host.checker.type_name(reference))
+    }
+}
+
+fn name(host: &VirtualFileHost, reference: &SymbolRef) -> String {
+    host.checker.type_name(reference)
+}
+
+#[test]
+fn instances_have_concrete_kinds() {
+    let host = checked("
+    struct Vec<T> {
+        first: T,
+        rest: [T, str]
+    }
+
+    struct Holder {
+        numbers: Vec<i32>
+    }
+    ");
+    let numbers = field(&host, &get(&host, "Holder"), "numbers");
+    assert_eq!(name(&host, &numbers), "Vec<i32>");
+    assert_eq!(field(&host, &numbers, "first"), host.checker.get_primitive("i32"));
+    assert_eq!(name(&host, &field(&host, &numbers, "rest")), "[i32, str]");
+    // The declaration itself keeps the type parameter
+    assert!(field(&host, &get(&host, "Vec"), "first").is_type_param());
+}
+
+#[test]
+fn instances_are_memoized() {
+    let host = checked("
+    struct Box<T> {
+        value: T
+    }
+
+    struct Holder {
+        a: Box<i32>,
+        b: Box<i32>,
+        c: Box<str>,
+        d: Box<Box<i32>>
+    }
+    ");
+    let holder = get(&host, "Holder");
+    let (a, b, c, d) = (field(&host, &holder, "a"), field(&host, &holder, "b"), field(&host, &holder, "c"), field(&host, &holder, "d"));
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert_ne!(a, d);
+    assert_eq!(get(&host, "Box").to_symbol(&host.checker).instances.len(), 3);
+}
+
+#[test]
+fn nested_instances_are_substituted() {
+    let host = checked("
+    struct Box<T> {
+        value: T
+    }
+
+    struct Pair<A, B> {
+        first: A,
+        second: B
+    }
+
+    struct Wrapper<T> {
+        inner: Box<Pair<T, Box<T>>>
+    }
+
+    struct Holder {
+        item: Wrapper<str>
+    }
+    ");
+    let item = field(&host, &get(&host, "Holder"), "item");
+    let inner = field(&host, &item, "inner");
+    assert_eq!(name(&host, &inner), "Box<Pair<str, Box<str>>>");
+    let pair = field(&host, &inner, "value");
+    assert_eq!(field(&host, &pair, "first"), host.checker.get_primitive("str"));
+    let boxed = field(&host, &pair, "second");
+    assert_eq!(name(&host, &boxed), "Box<str>");
+    assert_eq!(field(&host, &boxed, "value"), host.checker.get_primitive("str"));
+}
+
+#[test]
+fn recursive_instances_refer_to_themselves() {
+    let host = checked("
+    struct Node<T> {
+        value: T,
+        next: Node<T>?
+    }
+
+    struct List {
+        head: Node<i32>
+    }
+    ");
+    let head = field(&host, &get(&host, "List"), "head");
+    let next = field(&host, &head, "next");
+    assert!(next.is_optional());
+    assert_eq!(next.instance_id, head.instance_id);
+    assert_eq!(field(&host, &next, "value"), host.checker.get_primitive("i32"));
+}
+
+#[test]
+fn instances_of_declarations_which_are_being_checked() {
+    let host = checked("
+    struct Outer<T> {
+        holder: Holder,
+        value: T
+    }
+
+    struct Holder {
+        outer: Outer<bool>
+    }
+    ");
+    let outer = field(&host, &get(&host, "Holder"), "outer");
+    assert_eq!(field(&host, &outer, "value"), host.checker.get_primitive("bool"));
+}
+
+#[test]
+fn generic_aliases_are_substituted() {
+    let host = checked("
+    type Pair<T> = [T, T]
+
+    type Iterator<T> = {
+        next: () -> T?
+    }
+
+    struct Holder {
+        pair: Pair<i32>,
+        iter: Iterator<str>
+    }
+    ");
+    let holder = get(&host, "Holder");
+    assert_eq!(name(&host, &field(&host, &holder, "pair")), "[i32, i32]");
+    assert_eq!(name(&host, &field(&host, &field(&host, &holder, "iter"), "next")), "() -> str?");
+}
+
+#[test]
+fn wrong_amount_of_type_arguments() {
+    assert_eq!(error_codes("
+    struct Box<T> {
+        value: T
+    }
+
+    struct Holder {
+        item: Box<i32, str>
+    }
+    "), vec![Some(2007)]);
+}
+
+#[test]
+fn type_arguments_are_checked_against_bounds() {
+    let declarations = "
+    struct Error<T: { to_string: () -> str }> {
+        value: T
+    }
+
+    struct Code {
+        to_string: () -> str,
+        line: i32
+    }
+
+    struct Line {
+        to_string: () -> i32
+    }
+    ";
+    assert_eq!(error_codes(&format!("{} struct A {{ e: Error<Code> }}", declarations)), vec![]);
+    assert_eq!(error_codes(&format!("{} struct A {{ e: Error<i32> }}", declarations)), vec![Some(2014)]);
+    assert_eq!(error_codes(&format!("{} struct A {{ e: Error<Line> }}", declarations)), vec![Some(2015)]);
+    // Type parameters satisfy a bound if their own constraint does
+    assert_eq!(error_codes(&format!("{} struct A<T> {{ e: Error<T> }}", declarations)), vec![Some(2014)]);
+    assert_eq!(error_codes(&format!("{} struct A<T: {{ to_string: () -> str }}> {{ e: Error<T> }}", declarations)), vec![]);
+}
+
+#[test]
+fn type_arguments_of_calls_are_checked_against_bounds() {
+    let declarations = "
+    struct Code {
+        to_string: () -> str,
+        line: i32
+    }
+
+    struct Line {
+        to_string: () -> i32
+    }
+    ";
+    let call = |args: &str| main_errors(declarations, &format!("let f = fn<T: {{ to_string: () -> str }}>(v: T) -> T v; {}", args));
+    assert_eq!(call("f(new Code { to_string: fn() -> str \"a\", line: 1 });"), vec![]);
+    assert_eq!(call("let c = new Code { to_string: fn() -> str \"a\", line: 1 }; let d: Code = f(c);"), vec![]);
+    assert_eq!(call("f(1);"), vec![Some(2014)]);
+    assert_eq!(call("f(\"a\");"), vec![Some(2014)]);
+    assert_eq!(call("f(new Line { to_string: fn() -> i32 1 });"), vec![Some(2015)]);
+}
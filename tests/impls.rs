@@ -0,0 +1,83 @@
+mod common;
+use common::{diagnostics_with, error_codes_with, labels, main_errors};
+use lazy::semantic_analyzer::file_host::VirtualFileHost;
+
+const DECLARATIONS: &str = "
+type Speak = { speak: () -> str }
+
+struct Human {
+    name: str
+}
+
+struct Rock {
+    weight: i32
+}
+
+enum Option<T> {
+    Some: T,
+    None
+}
+
+type Unwrap<T> = { unwrap_or: (v: T) -> T }
+";
+
+#[test]
+fn implementations_add_members() {
+    let implementation = "impl Speak for Human { speak: fn() -> str \"Hello\" }";
+    assert_eq!(error_codes_with(DECLARATIONS, &format!("{}\nstruct Test {{ a: Speak = new Human {{ name: \"a\" }} }}", implementation)), vec![]);
+    assert_eq!(error_codes_with(DECLARATIONS, "struct Test { a: Speak = new Human { name: \"a\" } }"), vec![Some(2016)]);
+    assert_eq!(error_codes_with(DECLARATIONS, &format!("{}\nstruct Test {{ a: Speak = new Rock {{ weight: 1 }} }}", implementation)), vec![Some(2016)]);
+}
+
+#[test]
+fn impl_types() {
+    let implementation = "impl Speak for Human { speak: fn() -> str \"Hello\" }";
+    assert_eq!(error_codes_with(DECLARATIONS, &format!("{}\nstruct Test {{ a: impl Speak = new Human {{ name: \"a\" }} }}", implementation)), vec![]);
+    assert_eq!(error_codes_with(DECLARATIONS, "struct Test { a: impl Speak = new Human { name: \"a\" } }"), vec![Some(2016)]);
+    assert_eq!(error_codes_with(DECLARATIONS, "struct Test { a: impl i32 = 1 }"), vec![Some(2020)]);
+    let call = |args: &str| main_errors(&format!("{}\n{}", DECLARATIONS, implementation), &format!("let say = fn(s: impl Speak) -> str s.speak(); say({});", args));
+    assert_eq!(call("new Human { name: \"a\" }"), vec![]);
+    assert_eq!(call("new Rock { weight: 1 }"), vec![Some(2016)]);
+    assert_eq!(call("1"), vec![Some(2011)]);
+}
+
+#[test]
+fn implementations_are_checked_against_the_partial() {
+    assert_eq!(error_codes_with(DECLARATIONS, "impl Speak for Human {}"), vec![Some(2016)]);
+    assert_eq!(error_codes_with(DECLARATIONS, "impl Speak for Human { speak: fn() -> i32 1 }"), vec![Some(2017)]);
+    assert_eq!(error_codes_with(DECLARATIONS, "impl Speak for i32 { speak: fn() -> str \"a\" }"), vec![Some(2021)]);
+    assert_eq!(error_codes_with(DECLARATIONS, "impl i32 for Human {}"), vec![Some(2020)]);
+}
+
+#[test]
+fn conflicting_implementations() {
+    let implementation = "impl Speak for Human { speak: fn() -> str \"Hello\" }";
+    assert_eq!(error_codes_with(DECLARATIONS, &format!("{}\n{}", implementation, implementation)), vec![Some(2019)]);
+    let generic = "impl<T> Unwrap<T> for Option<T> { unwrap_or: fn(v: T) -> T v }";
+    let concrete = "impl Unwrap<i32> for Option<i32> { unwrap_or: fn(v: i32) -> i32 v }";
+    assert_eq!(error_codes_with(DECLARATIONS, generic), vec![]);
+    assert_eq!(error_codes_with(DECLARATIONS, concrete), vec![]);
+    assert_eq!(error_codes_with(DECLARATIONS, &format!("{}\n{}", generic, concrete)), vec![Some(2019)]);
+}
+
+#[test]
+fn conflicting_implementations_across_modules() {
+    let mut host = VirtualFileHost::new();
+    host.add_to_cache("speech", String::from("export type Speak = { speak: () -> str }\nexport struct Human { name: str }\nimpl Speak for Human { speak: fn() -> str \"Hello\" }"));
+    let errors = diagnostics_with(&mut host, "import { Speak, Human } from \"./speech\"\nimpl Speak for Human { speak: fn() -> str \"Hi\" }");
+    assert_eq!(errors.iter().map(|err| err.code).collect::<Vec<Option<u16>>>(), vec![Some(2019)]);
+    assert_eq!(labels(&host, &errors[0]), vec![(String::from("First implemented here"), String::from("speech"), String::from("Human"))]);
+}
+
+#[test]
+fn implementations_for_instances() {
+    let implementation = "impl Unwrap<i32> for Option<i32> { unwrap_or: fn(v: i32) -> i32 v }";
+    assert_eq!(error_codes_with(DECLARATIONS, &format!("{}\nstruct Test {{ a: Unwrap<i32> = Option::Some<i32>(1) }}", implementation)), vec![]);
+    assert_eq!(error_codes_with(DECLARATIONS, &format!("{}\nstruct Test {{ a: Unwrap<str> = Option::Some<str>(\"a\") }}", implementation)), vec![Some(2016)]);
+    let generic = "impl<T> Unwrap<T> for Option<T> { unwrap_or: fn(v: T) -> T v }";
+    assert_eq!(error_codes_with(DECLARATIONS, &format!("{}\nstruct Test {{ a: Unwrap<str> = Option::Some<str>(\"a\") }}", generic)), vec![]);
+    // The type arguments of the variant are inferred from its value
+    assert_eq!(error_codes_with(DECLARATIONS, &format!("{}\nstruct Test {{ a: Unwrap<i32> = Option::Some(1) }}", implementation)), vec![]);
+    assert_eq!(error_codes_with(DECLARATIONS, &format!("{}\nstruct Test {{ a: Unwrap<str> = Option::Some(\"a\") }}", generic)), vec![]);
+    assert_eq!(error_codes_with(DECLARATIONS, &format!("{}\nstruct Test {{ a: Unwrap<str> = Option::Some(\"a\") }}", implementation)), vec![Some(2016)]);
+}
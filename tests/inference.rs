@@ -0,0 +1,155 @@
+mod common;
+use common::{error_codes_with, main_errors};
+
+const DECLARATIONS: &str = "
+struct Point {
+    x: i32,
+    y: i32 = 0,
+    sum: fn() -> i32 self.x + self.y
+}
+
+enum Option<T> {
+    Some: T,
+    None
+}
+";
+
+#[test]
+fn variables_without_annotations() {
+    assert_eq!(main_errors(DECLARATIONS, "let a = 1; let b: i32 = a;"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let a = \"a\"; let b: i32 = a;"), vec![Some(2011)]);
+    assert_eq!(main_errors(DECLARATIONS, "let a: str = 1;"), vec![Some(2011)]);
+    assert_eq!(main_errors(DECLARATIONS, "let a = b;"), vec![Some(2005)]);
+    assert_eq!(main_errors(DECLARATIONS, "let a = Point;"), vec![Some(2022)]);
+}
+
+#[test]
+fn operators() {
+    assert_eq!(main_errors(DECLARATIONS, "let a: f32 = 1.5 * 2; let b: bool = a > 1 && !false;"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let a: str = \"a\" + \"b\";"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let a = 1 + \"a\";"), vec![Some(2023)]);
+    assert_eq!(main_errors(DECLARATIONS, "let a = 1.5 & 2.5;"), vec![Some(2023)]);
+    assert_eq!(main_errors(DECLARATIONS, "let a = !1;"), vec![Some(2024)]);
+    assert_eq!(main_errors(DECLARATIONS, "let a = 1; a += \"b\";"), vec![Some(2023)]);
+    assert_eq!(main_errors(DECLARATIONS, "let a: str? = none; let b: str = a || \"b\";"), vec![]);
+}
+
+#[test]
+fn calls_and_members() {
+    assert_eq!(main_errors(DECLARATIONS, "let p = new Point { x: 1 }; let a: i32 = p.x + p.sum();"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let p = new Point { x: 1 }; let a = p.z;"), vec![Some(2025)]);
+    assert_eq!(main_errors(DECLARATIONS, "let p: Point? = none; let a: i32 = p.x;"), vec![Some(2011)]);
+    assert_eq!(main_errors(DECLARATIONS, "let add = fn(a: i32) -> i32 a + 1; let b: str = add(1);"), vec![Some(2011)]);
+    assert_eq!(main_errors(DECLARATIONS, "let f: (a: i32) -> i32 = fn(a) a * 2;"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let f: (a: i32) -> str = fn(a) a;"), vec![Some(2011)]);
+    assert_eq!(main_errors(DECLARATIONS, "let a = 1; a();"), vec![Some(2024)]);
+}
+
+#[test]
+fn blocks_and_branches() {
+    assert_eq!(main_errors(DECLARATIONS, "let a: i32 = { let b = 2; b * 2; };"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let a: str = { let b = 2; b; };"), vec![Some(2011)]);
+    assert_eq!(main_errors(DECLARATIONS, "let a: str = if 1 > 2 { \"a\"; } else { \"b\"; };"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let a = if true { 1; } else { \"a\"; };"), vec![Some(2011)]);
+    assert_eq!(main_errors(DECLARATIONS, "let a = if true { 1; }; let b: i32? = a;"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let a: i32 = if true { 1; };"), vec![Some(2011)]);
+    assert_eq!(main_errors(DECLARATIONS, "if 1 { 1; };"), vec![Some(2011)]);
+    assert_eq!(main_errors(DECLARATIONS, "if true { 1; } else { \"a\"; };"), vec![]);
+}
+
+#[test]
+fn match_arms() {
+    let value = "let a = Option::Some<i32>(1);";
+    assert_eq!(main_errors(DECLARATIONS, &format!("{} let b: i32 = match a {{ Option::Some(v) => v + 1, Option::None => 0 }};", value)), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, &format!("{} let b = match a {{ Option::Some(v) => v, Option::None => \"a\" }};", value)), vec![Some(2011)]);
+    assert_eq!(main_errors(DECLARATIONS, &format!("{} let b: str = match a {{ Option::Some(v) => v, Option::None => \"a\" }};", value)), vec![Some(2011)]);
+    assert_eq!(main_errors(DECLARATIONS, "let b = match 1 { \"a\" => 1, _ => 2 };"), vec![Some(2011)]);
+}
+
+#[test]
+fn variants_without_type_args() {
+    assert_eq!(main_errors(DECLARATIONS, "let o: Option<i32> = Option::Some(1);"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let o: Option<i32> = Option::None;"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let o: Option<i32>? = Option::None;"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let o: Option<i32> = Option::Some(\"a\");"), vec![Some(2011)]);
+    assert_eq!(main_errors(DECLARATIONS, "let f = fn(o: Option<i32>) -> i32 1; f(Option::None); f(Option::Some(1));"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let f = fn(o: Option<i32>) -> i32 1; f(Option::Some(\"a\"));"), vec![Some(2011)]);
+    // Without an expected type, the type arguments are inferred from the value
+    assert_eq!(main_errors(DECLARATIONS, "let a = Option::Some(1); let b: Option<i32> = a;"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let a = Option::Some(\"a\"); let b: Option<i32> = a;"), vec![Some(2011)]);
+    assert_eq!(main_errors(DECLARATIONS, "let a = Option::Some(1); let b: i32 = match a { Option::Some(v) => v + 1, Option::None => 0 };"), vec![]);
+}
+
+#[test]
+fn call_arguments() {
+    let add = "let add = fn(a: i32, b: i32) -> i32 a + b;";
+    assert_eq!(main_errors(DECLARATIONS, &format!("{} add(1, 2);", add)), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, &format!("{} add(1);", add)), vec![Some(2028)]);
+    assert_eq!(main_errors(DECLARATIONS, &format!("{} add();", add)), vec![Some(2028)]);
+    assert_eq!(main_errors(DECLARATIONS, &format!("{} add(1, 2, 3);", add)), vec![Some(2029)]);
+    assert_eq!(main_errors(DECLARATIONS, "let add = fn(a: i32, b: i32?) -> i32 a; add(1);"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let add = fn(a: i32, b: i32 = 2) -> i32 a + b; add(1); add(1, 2);"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let log = fn(...values: str?) {}; log(); log(\"a\", \"b\", \"c\");"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let log = fn(...values: str?) {}; log(\"a\", 1);"), vec![Some(2011)]);
+}
+
+#[test]
+fn nested_inferred_arguments() {
+    let id = "let id = fn<T>(v: T) -> T v;";
+    let call = format!("{}1{}", "id(".repeat(20), ")".repeat(20));
+    assert_eq!(main_errors(DECLARATIONS, &format!("{} let a: i32 = {};", id, call)), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, &format!("{} let a: str = {};", id, call)), vec![Some(2011)]);
+    let some = format!("{}1{}", "Option::Some(".repeat(20), ")".repeat(20));
+    assert_eq!(main_errors(DECLARATIONS, &format!("let a = {};", some)), vec![]);
+}
+
+#[test]
+fn missing_fields() {
+    assert_eq!(main_errors(DECLARATIONS, "let p = new Point {};"), vec![Some(2016)]);
+    assert_eq!(main_errors(DECLARATIONS, "let p = new Point { y: 1 };"), vec![Some(2016)]);
+    assert_eq!(main_errors(DECLARATIONS, "let p = new Point { x: 1, y: 2 };"), vec![]);
+    assert_eq!(error_codes_with(DECLARATIONS, "struct A { a: i32?, b = 1 }\nmain { let a = new A {}; }"), vec![]);
+    assert_eq!(error_codes_with(DECLARATIONS, "struct A { a: i32, b: str }\nmain { let a = new A { b: \"b\" }; }"), vec![Some(2016)]);
+}
+
+#[test]
+fn unknown_fields() {
+    assert_eq!(main_errors(DECLARATIONS, "let p = new Point { x: 1, z: 2 };"), vec![Some(2025)]);
+    let source = format!("{}\nmain {{ let p = new Point {{ x: 1, z: 2 }}; }}", DECLARATIONS);
+    let err = common::diagnostics(&source).into_iter().find(|err| err.is_error()).unwrap();
+    assert_eq!(&source[err.range.start.pos..err.range.end.pos], "z");
+}
+
+#[test]
+fn deconstructing() {
+    assert_eq!(main_errors(DECLARATIONS, "let [a, b] = [1, \"a\"]; let c: str = b;"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let [a, b, c] = [1, 2];"), vec![Some(2026)]);
+    assert_eq!(main_errors(DECLARATIONS, "let [a, ...rest] = [1, \"a\", 'c']; let [b, c] = rest; let d: char = c;"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let { x, y: z } = new Point { x: 1 }; let a: i32 = x + z;"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let { z } = new Point { x: 1 };"), vec![Some(2025)]);
+    assert_eq!(main_errors(DECLARATIONS, "let { x } = 1;"), vec![Some(2026)]);
+}
+
+#[test]
+fn format_specs() {
+    assert_eq!(main_errors(DECLARATIONS, "let a = 255; let b = `${a:x} ${a:+05} ${1.5:.2} ${\"a\":?}`;"), vec![]);
+    assert_eq!(main_errors(DECLARATIONS, "let a = `${\"a\":x}`;"), vec![Some(2027)]);
+    assert_eq!(main_errors(DECLARATIONS, "let a = `${1.5:b}`;"), vec![Some(2027)]);
+    assert_eq!(main_errors(DECLARATIONS, "let a = `${true:+}`;"), vec![Some(2027)]);
+}
+
+#[test]
+fn statics() {
+    assert_eq!(error_codes_with(DECLARATIONS, "static ORIGIN = new Point { x: 0 }\nmain { let a: i32 = ORIGIN.x; }"), vec![]);
+    assert_eq!(error_codes_with(DECLARATIONS, "static SCALE<f32> = 2\nmain { let a: f32 = SCALE * 1.5; }"), vec![]);
+    assert_eq!(error_codes_with(DECLARATIONS, "static SCALE<f32> = \"a\""), vec![Some(2011)]);
+    assert_eq!(error_codes_with(DECLARATIONS, "static A = A"), vec![Some(2009)]);
+    assert_eq!(error_codes_with(DECLARATIONS, "static A = 1\nstruct Test { a: A }"), vec![Some(2006)]);
+}
+
+#[test]
+fn methods() {
+    assert_eq!(error_codes_with(DECLARATIONS, "struct Test { a: i32, get: fn() -> str self.a }"), vec![Some(2011)]);
+    assert_eq!(error_codes_with(DECLARATIONS, "struct Test { a: i32, get: fn() -> i32 self.b }"), vec![Some(2025)]);
+    assert_eq!(error_codes_with(DECLARATIONS, "struct Test { a: i32, get: fn() -> i32 self.a * 2 }"), vec![]);
+}
@@ -0,0 +1,66 @@
+use lazy::errors::*;
+use lazy::errors::builder::ErrorFormatter;
+use serde_json::{json, Value};
+
+fn loc(file: FileId, line: usize, col: usize, pos: usize) -> LoC {
+    LoC { line, col, pos, file }
+}
+
+fn to_json(map: &SourceMap, err: &BaseError) -> Value {
+    let line = map.format_err_json(err);
+    assert!(!line.contains('\n'));
+    serde_json::from_str(&line).unwrap()
+}
+
+#[test]
+fn diagnostic_shape() {
+    let mut map = SourceMap::new();
+    let file = map.add("main.lazy", "main {\n    let a = b;\n}".to_string());
+    let err = err!(NAME_NOT_FOUND, loc(file, 2, 12, 19).end(&loc(file, 2, 13, 20)), "b"; ["Declare the variable first"]);
+    let json = to_json(&map, &err);
+    assert_eq!(json["code"], "E2005");
+    assert_eq!(json["severity"], "error");
+    assert_eq!(json["file"], "main.lazy");
+    assert_eq!(json["range"], json!({
+        "start": { "line": 2, "column": 13, "byte": 19 },
+        "end": { "line": 2, "column": 14, "byte": 20 }
+    }));
+    assert_eq!(json["labels"], json!([{ "kind": "help", "message": "Declare the variable first", "file": "main.lazy", "range": null }]));
+    assert_eq!(json["fixes"], json!([]));
+}
+
+#[test]
+fn byte_offsets_count_utf8_bytes() {
+    let mut map = SourceMap::new();
+    let file = map.add("main.lazy", "let 名前 = b;".to_string());
+    let err = err!(NAME_NOT_FOUND, loc(file, 1, 9, 9).end(&loc(file, 1, 10, 10)), "b");
+    let json = to_json(&map, &err);
+    assert_eq!(json["range"]["start"], json!({ "line": 1, "column": 10, "byte": 13 }));
+    assert_eq!(json["range"]["end"], json!({ "line": 1, "column": 11, "byte": 14 }));
+}
+
+#[test]
+fn labels_and_fixes_in_other_files() {
+    let mut map = SourceMap::new();
+    let songs = map.add("songs.lazy", "// 😀😀\nstruct Song {}".to_string());
+    let main = map.add("main.lazy", "import { Song } from \"./songs\"".to_string());
+    let err = err!(TYPE_NOT_FOUND_FROM_MOD, loc(main, 1, 9, 9).end(&loc(main, 1, 13, 13)), "Song", "./songs")
+        .with_label("Declared here", loc(songs, 2, 7, 13).end(&loc(songs, 2, 11, 17)))
+        .with_suggestion("Export the struct", loc(songs, 2, 0, 6).to_range(), "export ", Applicability::MaybeIncorrect);
+    let json = to_json(&map, &err);
+    assert_eq!(json["file"], "main.lazy");
+    assert_eq!(json["labels"][0]["kind"], "secondary");
+    assert_eq!(json["labels"][0]["file"], "songs.lazy");
+    assert_eq!(json["labels"][0]["range"]["start"], json!({ "line": 2, "column": 8, "byte": 19 }));
+    // The byte offsets of the fix are calculated from the file it changes, not the file of the diagnostic
+    assert_eq!(json["fixes"], json!([{
+        "message": "Export the struct",
+        "replacement": "export ",
+        "file": "songs.lazy",
+        "range": {
+            "start": { "line": 2, "column": 1, "byte": 12 },
+            "end": { "line": 2, "column": 1, "byte": 12 }
+        },
+        "applicability": "maybe-incorrect"
+    }]));
+}
@@ -0,0 +1,145 @@
+mod common;
+use common::{diagnostics, diagnostics_with};
+use lazy::semantic_analyzer::{file_host::VirtualFileHost, lints::{LintConfig, LintLevel, check_module}};
+use lazy::parser::ast::parse_statements_str;
+use lazy::errors::{ErrorCollector, FileId};
+
+// The codes of the warnings and errors, and whether they're errors
+fn lints(source: &str) -> Vec<(Option<u16>, bool)> {
+    diagnostics(source).iter().map(|err| (err.code, err.is_error())).collect()
+}
+
+fn warns(source: &str, code: u16) {
+    assert_eq!(lints(source), vec![(Some(code), false)], "expected E{} in {:?}", code, source);
+}
+
+fn clean(source: &str) {
+    assert_eq!(lints(source), vec![], "expected no diagnostics in {:?}", source);
+}
+
+#[test]
+fn unused_imports() {
+    let mut host = VirtualFileHost::new();
+    host.add_to_cache("songs", String::from("export struct Song { name: str }\nexport struct Album { name: str }"));
+    let codes = |host: &mut VirtualFileHost, source: &str| diagnostics_with(host, source).iter().map(|err| err.code).collect::<Vec<Option<u16>>>();
+    assert_eq!(codes(&mut host, "import { Song, Album } from \"./songs\"\nexport type A = Song"), vec![Some(3001)]);
+    let mut host = VirtualFileHost::new();
+    host.add_to_cache("songs", String::from("export struct Song { name: str }\nexport struct Album { name: str }"));
+    assert_eq!(codes(&mut host, "import { Song, Album as _Album } from \"./songs\"\nexport type A = Song"), vec![]);
+}
+
+#[test]
+fn unused_variables() {
+    warns("main { let a = 1; }", 3002);
+    warns("export struct A { f: fn(a: i32) -> i32 1 }", 3002);
+    clean("main { let a = 1; a + 1; }");
+    clean("main { let _a = 1; }");
+    clean("export struct A { f: fn(a: i32) -> i32 a }");
+}
+
+#[test]
+fn shadowed_names() {
+    warns("main { let a = 1; { let a = 2; a + 1; }; a + 1; }", 3003);
+    clean("main { let a = 1; { let b = 2; b + 1; }; a + 1; }");
+    clean("main { { let a = 1; a + 1; }; { let a = 2; a + 1; }; }");
+}
+
+#[test]
+fn non_snake_case_fields() {
+    warns("export struct A { someField: i32 }", 3004);
+    clean("export struct A { some_field: i32 }");
+}
+
+#[test]
+fn non_camel_case_types() {
+    warns("export struct some_struct { a: i32 }", 3005);
+    warns("export enum A { some_variant }", 3005);
+    warns("export type some_type = i32", 3005);
+    clean("export enum SomeEnum { SomeVariant }");
+}
+
+#[test]
+fn unused_type_params() {
+    warns("export struct A<T> { a: i32 }", 3006);
+    clean("export struct A<T> { a: T }");
+    clean("export struct A<_T> { a: i32 }");
+}
+
+#[test]
+fn dead_code() {
+    warns("struct A { a: i32 }", 3007);
+    // Recursive types don't count as used
+    warns("struct A { a: A? }", 3007);
+    clean("export struct A { a: i32 }");
+    clean("struct A { a: i32 }\nexport type B = A");
+    clean("struct _A { a: i32 }");
+}
+
+#[test]
+fn self_assignment() {
+    warns("main { let a = 1; a = a; }", 3008);
+    clean("main { let a = 1; let b = 2; a = b; }");
+}
+
+#[test]
+fn pointless_template() {
+    warns("main { let _a = `a`; }", 1024);
+    clean("main { let a = 1; let _b = `${a}`; }");
+}
+
+#[test]
+fn unknown_lints() {
+    warns("#allow(not_a_lint)\nexport struct A { a: i32 }", 3009);
+    clean("#allow(dead_code)\nstruct A { a: i32 }");
+}
+
+#[test]
+fn attribute_levels() {
+    clean("#allow(dead_code)\nstruct A { a: i32 }");
+    assert_eq!(lints("#deny(dead_code)\nstruct A { a: i32 }"), vec![(Some(3007), true)]);
+    // Attributes only change the level of the statement they're on
+    assert_eq!(lints("#allow(dead_code)\nstruct A { a: i32 }\nstruct B { a: i32 }"), vec![(Some(3007), false)]);
+    assert_eq!(lints("#deny(non_snake_case_fields)\n#allow(dead_code)\nstruct A { someField: i32 }"), vec![(Some(3004), true)]);
+    // Inner attributes win over outer ones
+    clean("#deny(dead_code)\n#allow(dead_code)\nstruct A { a: i32 }");
+    assert_eq!(lints("#warn(dead_code, unused_type_params)\nstruct A<T> { a: i32 }").len(), 2);
+}
+
+#[test]
+fn configured_levels() {
+    let mut config = LintConfig::new();
+    assert!(config.set("dead_code", LintLevel::Deny));
+    assert!(config.set("unused_variables", LintLevel::Allow));
+    assert!(!config.set("not_a_lint", LintLevel::Allow));
+    let mut host = VirtualFileHost::new();
+    host.lints = config.clone();
+    let diagnostics = diagnostics_with(&mut host, "struct A { a: i32 }");
+    assert_eq!(diagnostics.iter().map(|err| (err.code, err.is_error())).collect::<Vec<(Option<u16>, bool)>>(), vec![(Some(3007), true)]);
+    let mut host = VirtualFileHost::new();
+    host.lints = config.clone();
+    assert_eq!(diagnostics_with(&mut host, "main { let a = 1; }").len(), 0);
+    // Attributes win over the configuration
+    let mut host = VirtualFileHost::new();
+    host.lints = config;
+    assert_eq!(diagnostics_with(&mut host, "#warn(dead_code)\nstruct A { a: i32 }").iter().map(|err| (err.code, err.is_error())).collect::<Vec<(Option<u16>, bool)>>(), vec![(Some(3007), false)]);
+}
+
+// Only runs the lints, on a thread with the usual 8 MB main thread stack, like the parser tests for long chains
+fn chain_lints(source: String) -> Vec<Option<u16>> {
+    std::thread::Builder::new().stack_size(8 * 1024 * 1024).spawn(move || {
+        let (ast, errors) = parse_statements_str(&source);
+        assert_eq!(errors.len(), 0);
+        let mut collector = ErrorCollector::new(FileId::default());
+        check_module(&ast, &mut collector);
+        collector.collected.iter().map(|err| err.code).collect()
+    }).unwrap().join().unwrap()
+}
+
+#[test]
+fn long_chains() {
+    assert_eq!(chain_lints(format!("main {{ let a = 1; let _b = a{}; }}", " + a".repeat(20000))), vec![]);
+    assert_eq!(chain_lints(format!("main {{ let a = 1; let b = a{}; }}", " + 1".repeat(20000))), vec![Some(3002)]);
+    assert_eq!(chain_lints(format!("main {{ let a = 1; a{}; }}", ".c()".repeat(20000))), vec![]);
+    assert_eq!(chain_lints(format!("main {{ let a = 1; let c = 2; a{}(c); }}", ".b".repeat(20000))), vec![]);
+    assert_eq!(chain_lints(format!("main {{ let a = 1; a{0} = a{0}; }}", ".b".repeat(20000))), vec![Some(3008)]);
+}
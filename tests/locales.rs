@@ -0,0 +1,25 @@
+use lazy::errors::{diagnostics::{Diagnostics, format_code}, locale::{get_message_in, CATALOGS}};
+
+#[test]
+fn every_catalog_has_every_message() {
+    for (lang, _) in CATALOGS {
+        for diagnostic in Diagnostics::ALL {
+            let args = diagnostic.args.iter().map(|arg| (*arg, format!("<{}>", arg))).collect::<Vec<(&str, String)>>();
+            let args = args.iter().map(|(name, value)| (*name, value.as_str())).collect::<Vec<(&str, &str)>>();
+            let message = get_message_in(lang, diagnostic.code, &args).unwrap_or_else(|error| panic!("{}", error));
+            for (name, value) in &args {
+                assert!(message.contains(value), "{} in the \"{}\" catalog doesn't use the \"{}\" argument.", format_code(diagnostic.code), lang, name);
+            }
+        }
+    }
+}
+
+#[test]
+fn catalogs_only_have_known_codes() {
+    for (lang, source) in CATALOGS {
+        for key in source.lines().filter_map(|line| line.split_once(" = ")).map(|(key, _)| key) {
+            let known = Diagnostics::ALL.iter().any(|diagnostic| format_code(diagnostic.code) == key);
+            assert!(known, "The \"{}\" catalog has a message for {}, which isn't a diagnostic.", lang, key);
+        }
+    }
+}
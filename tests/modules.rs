@@ -0,0 +1,62 @@
+mod common;
+use common::labels;
+use lazy::semantic_analyzer::file_host::{VirtualFileHost, FileHost};
+use lazy::errors::BaseError;
+
+fn host_with(modules: &[(&str, &str)]) -> VirtualFileHost {
+    let mut host = VirtualFileHost::new();
+    for (path, source) in modules {
+        host.add_to_cache(path, source.to_string());
+    }
+    host
+}
+
+fn errors_of(host: &mut VirtualFileHost, source: &str) -> Vec<BaseError> {
+    match host.create_virtual("main", source.to_string()) {
+        Ok(_) => vec![],
+        Err(errors) => errors.collected.into_iter().filter(|err| err.is_error()).collect()
+    }
+}
+
+#[test]
+fn import_cycles() {
+    let mut host = host_with(&[
+        ("a", "import { B } from \"./b\"\nexport struct A { b: B }"),
+        ("b", "import { A } from \"./a\"\nexport struct B { a: A? }")
+    ]);
+    let errors = errors_of(&mut host, "import { A } from \"./a\"\nmain { let a: A? = none; }");
+    assert_eq!(errors.iter().map(|err| err.code).collect::<Vec<Option<u16>>>(), vec![Some(2004)]);
+    assert_eq!(labels(&host, &errors[0]), vec![(String::from("Imported here"), String::from("a"), String::from("\"./b\""))]);
+    // The entry module can be a part of the cycle too
+    let mut host = host_with(&[("b", "import { A } from \"./main\"\nexport struct B { a: A? }")]);
+    let errors = errors_of(&mut host, "import { B } from \"./b\"\nexport struct A { b: B }");
+    assert_eq!(errors.iter().map(|err| err.code).collect::<Vec<Option<u16>>>(), vec![Some(2004)]);
+    assert!(host.get("b").is_none());
+}
+
+#[test]
+fn modules_are_only_checked_once() {
+    let mut host = host_with(&[
+        ("shapes", "export struct Circle { radius: f32 }"),
+        ("a", "import { Circle } from \"./shapes\"\nexport struct A { c: Circle }")
+    ]);
+    assert_eq!(errors_of(&mut host, "import { Circle } from \"./shapes\"\nimport { A } from \"./a\"\nmain { let a: A? = none; let c: Circle? = none; }").len(), 0);
+    assert_eq!(host.files.len(), 3);
+}
+
+#[test]
+fn failed_modules_are_checked_again() {
+    let mut host = host_with(&[("a", "export struct A { b: B }")]);
+    let codes = |host: &mut VirtualFileHost| errors_of(host, "import { A } from \"./a\"\nmain { let a: A? = none; }").iter().map(|err| err.code).collect::<Vec<Option<u16>>>();
+    assert_eq!(codes(&mut host), vec![Some(2005)]);
+    // Used to report that the module doesn't exist, because its source was removed from the cache
+    assert_eq!(codes(&mut host), vec![Some(2005)]);
+}
+
+#[test]
+fn names_defined_in_other_modules() {
+    let mut host = host_with(&[("songs", "export struct Song { name: str }")]);
+    let errors = errors_of(&mut host, "import { Song } from \"./songs\"\nstruct Song { title: str }");
+    assert_eq!(errors.iter().map(|err| err.code).collect::<Vec<Option<u16>>>(), vec![Some(2003)]);
+    assert_eq!(labels(&host, &errors[0]), vec![(String::from("First defined here"), String::from("songs"), String::from("Song"))]);
+}
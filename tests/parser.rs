@@ -0,0 +1,175 @@
+use lazy::parser::ast::{parse_expression_str, parse_expression_str_with_depth, parse_type_str, parse_statements_str, model::{ASTExpression, ASTStatement}, DEFAULT_MAX_DEPTH};
+use lazy::semantic_analyzer::file_host::VirtualFileHost;
+use lazy::errors::{FileId, fix::apply_machine_applicable};
+
+fn error_codes(source: &str) -> Vec<Option<u16>> {
+    parse_expression_str(source).1.iter().filter(|err| err.is_error()).map(|err| err.code).collect()
+}
+
+// Deeply nested code is parsed on a thread with the usual 8 MB main thread stack, instead of the smaller test thread stack
+fn deep_error_codes(source: String) -> Vec<Option<u16>> {
+    std::thread::Builder::new().stack_size(8 * 1024 * 1024).spawn(move || error_codes(&source)).unwrap().join().unwrap()
+}
+
+// Runs the whole pipeline for the module on a thread with the usual 8 MB main thread stack
+fn deep_checked_codes(source: String) -> Vec<Option<u16>> {
+    std::thread::Builder::new().stack_size(8 * 1024 * 1024).spawn(move || match VirtualFileHost::new().create_virtual("main", source) {
+        Ok(_) => vec![],
+        Err(errors) => errors.collected.iter().filter(|err| err.is_error()).map(|err| err.code).collect()
+    }).unwrap().join().unwrap()
+}
+
+fn let_pattern(source: &str) -> String {
+    match parse_expression_str(source) {
+        (Some(ASTExpression::Declare(decl)), errors) if errors.is_empty() => decl.var.to_string(),
+        (_, errors) => panic!("\"{}\" isn't a declaration, found errors {:?}", source, errors.iter().map(|err| err.code).collect::<Vec<Option<u16>>>())
+    }
+}
+
+#[test]
+fn nested_deconstruct_patterns() {
+    assert_eq!(let_pattern("let [a, [b, c]] = value"), "[a, [b, c]]");
+    assert_eq!(let_pattern("let { a: [b, { c }], d } = value"), "{a: [b, {c}], d}");
+    assert_eq!(let_pattern("let { a: renamed } = value"), "{a: renamed}");
+}
+
+#[test]
+fn deconstruct_defaults_and_rest() {
+    assert_eq!(let_pattern("let [a = 1, b] = value"), "[a = 1, b]");
+    assert_eq!(let_pattern("let { a = 1, b: c = 2 } = value"), "{a = 1, b: c = 2}");
+    assert_eq!(let_pattern("let [a, ...rest] = value"), "[a, ...rest]");
+    assert_eq!(let_pattern("let { a, ...rest } = value"), "{a, ...rest}");
+}
+
+#[test]
+fn invalid_deconstruct_patterns() {
+    assert_eq!(error_codes("let [...a, b] = value"), vec![Some(1030)]);
+    assert_eq!(error_codes("let { ...a, b } = value"), vec![Some(1030)]);
+    assert_eq!(error_codes("let [...a = 1] = value"), vec![Some(1018)]);
+    assert_eq!(error_codes("let [a, 1] = value"), vec![Some(1009)]);
+}
+
+#[test]
+fn template_format_specs() {
+    assert_eq!(error_codes("`${a::b:>3}`"), vec![]);
+    assert_eq!(error_codes("`${a::b::c}`"), vec![]);
+    assert_eq!(error_codes("`${if a {1;} else {2;}:>3}`"), vec![]);
+    assert_eq!(error_codes("`${if a { let b: i32 = 1; b; } else {2;}:>3}`"), vec![]);
+    assert_eq!(error_codes("`${a.b:x} ${c(1):?}`"), vec![]);
+}
+
+#[test]
+fn pointless_templates_become_string_literals() {
+    let source = r#"`C:\\dir \``"#;
+    let (_, errors) = parse_expression_str(source);
+    assert_eq!(errors.iter().map(|err| err.code).collect::<Vec<Option<u16>>>(), vec![Some(1024)]);
+    let (fixed, applied) = apply_machine_applicable(source, FileId::default(), &errors);
+    assert_eq!(applied, 1);
+    assert_eq!(fixed, r#""C:\dir `""#);
+    match parse_expression_str(&fixed) {
+        (Some(ASTExpression::Str(string)), errors) if errors.is_empty() => assert_eq!(string.value, r#"C:\dir `"#),
+        _ => panic!("{} isn't a string literal", fixed)
+    }
+    // String literals can't contain quotes
+    let (_, errors) = parse_expression_str(r#"`say "hi"`"#);
+    assert_eq!(errors.iter().map(|err| (err.code, err.suggestions().count())).collect::<Vec<(Option<u16>, usize)>>(), vec![(Some(1024), 0)]);
+}
+
+#[test]
+fn parsing_lone_expressions_and_typings() {
+    assert!(matches!(parse_expression_str("1 + 2;"), (Some(ASTExpression::Binary(_)), errors) if errors.is_empty()));
+    assert_eq!(error_codes("1 + 2 3"), vec![Some(1009)]);
+    let (typing, errors) = parse_type_str("Vec<i32>?");
+    assert_eq!((typing.map(|typing| typing.to_string()), errors.len()), (Some(String::from("Vec<i32>?")), 0));
+    let (typing, errors) = parse_type_str("i32 str");
+    assert!(typing.is_none());
+    assert_eq!(errors.iter().map(|err| err.code).collect::<Vec<Option<u16>>>(), vec![Some(1009)]);
+}
+
+#[test]
+fn parsing_statements() {
+    let (statements, errors) = parse_statements_str("struct A { a: i32 }\nmain { let b = 1; }");
+    assert_eq!((statements.len(), errors.len()), (2, 0));
+    // Errors which skip the rest of the line used to panic on the last line of the input
+    let (_, errors) = parse_statements_str("main { let [...a, b] = c; }");
+    assert!(errors.iter().any(|err| err.code == Some(1030)));
+    let (_, errors) = parse_statements_str("a");
+    assert_eq!(errors.iter().map(|err| err.code).collect::<Vec<Option<u16>>>(), vec![Some(1009)]);
+}
+
+#[test]
+fn long_chains_are_not_nested() {
+    assert_eq!(deep_error_codes(format!("1{}", " + 1".repeat(300))), vec![]);
+    assert_eq!(deep_error_codes(format!("\"a\"{}", " + \"a\"".repeat(300))), vec![]);
+    assert_eq!(deep_error_codes(format!("1{}", " * 2 - 1 && a".repeat(100))), vec![]);
+    assert_eq!(deep_error_codes(format!("{}1", "- ".repeat(300))), vec![]);
+    assert_eq!(deep_error_codes(format!("{}a", "! ".repeat(300))), vec![]);
+    assert_eq!(deep_error_codes(format!("a{}", ".b()".repeat(300))), vec![]);
+    assert!(matches!(parse_expression_str("- ! a.b"), (Some(ASTExpression::Unary(unary)), _) if unary.to_string() == "-!a.b"));
+}
+
+#[test]
+fn long_chains_are_checked() {
+    let chain = "struct Chain { next: fn() -> Chain self, value: i32 = 1 }";
+    assert_eq!(deep_checked_codes(format!("main {{ let a = 1{}; }}", " + 1".repeat(600))), vec![]);
+    assert_eq!(deep_checked_codes(format!("main {{ let a: str = 1{}; }}", " + 1".repeat(600))), vec![Some(2011)]);
+    assert_eq!(deep_checked_codes(format!("main {{ let a = \"a\"{}; }}", " + \"a\"".repeat(600))), vec![]);
+    assert_eq!(deep_checked_codes(format!("{}\nmain {{ let a: i32 = new Chain {{}}{}.value; }}", chain, ".next()".repeat(300))), vec![]);
+    assert_eq!(deep_checked_codes(format!("{}\nmain {{ let a: str = new Chain {{}}{}.value; }}", chain, ".next()".repeat(300))), vec![Some(2011)]);
+}
+
+#[test]
+fn deep_nesting() {
+    assert_eq!(deep_error_codes(format!("{}1{}", "(".repeat(100), ")".repeat(100))), vec![]);
+    assert_eq!(deep_error_codes(format!("{}1{}", "(".repeat(200), ")".repeat(200))), vec![Some(1032)]);
+    assert_eq!(deep_error_codes(format!("{}1", "fn() ".repeat(100))), vec![]);
+    // Used to overflow the stack before reaching the limit
+    assert_eq!(deep_error_codes(format!("{}1", "fn() ".repeat(DEFAULT_MAX_DEPTH * 2))), vec![Some(1032)]);
+    assert_eq!(deep_error_codes(format!("{}1", "if a ".repeat(DEFAULT_MAX_DEPTH * 2))), vec![Some(1032)]);
+    assert_eq!(deep_error_codes(format!("{}1{}", "{".repeat(DEFAULT_MAX_DEPTH * 2), "}".repeat(DEFAULT_MAX_DEPTH * 2))), vec![Some(1032)]);
+}
+
+#[test]
+fn configurable_max_depth() {
+    let codes = |source: &str, max_depth: usize| parse_expression_str_with_depth(source, max_depth).1.iter().map(|err| err.code).collect::<Vec<Option<u16>>>();
+    assert_eq!(codes("[[1]]", 3), vec![]);
+    assert_eq!(codes("[[1]]", 2), vec![Some(1032)]);
+    assert_eq!(codes("1 + 2 + 3 + 4", 1), vec![]);
+    let mut host = VirtualFileHost::new();
+    host.max_depth = 2;
+    let errors = host.create_virtual("main", String::from("main { let a = [[1]]; }")).err().expect("expected the nesting to be too deep");
+    assert_eq!(errors.collected[0].code, Some(1032));
+}
+
+fn script_main(source: &str) -> (Vec<String>, usize) {
+    let (statements, errors) = parse_statements_str(source);
+    assert_eq!(errors.iter().map(|err| err.code).collect::<Vec<Option<u16>>>(), vec![], "errors in {:?}", source);
+    let mut main = statements.iter().filter_map(|statement| match statement {
+        ASTStatement::Main(main) => Some(main.expression.elements.iter().map(|exp| exp.to_string().trim_end().to_string()).collect::<Vec<String>>()),
+        _ => None
+    });
+    let body = main.next().expect("expected the script to have a main entry");
+    assert!(main.next().is_none());
+    (body, statements.len() - 1)
+}
+
+#[test]
+fn script_mode() {
+    assert_eq!(script_main("#script\nlet a = 1;\nprint(a);"), (vec![String::from("let a = 1"), String::from("print(a)")], 0));
+    assert_eq!(script_main("#script\nstruct A { a: i32 }\nlet a = 1;\nenum B { C }\nprint(a);\ntype D = A"), (vec![String::from("let a = 1"), String::from("print(a)")], 3));
+    assert_eq!(script_main("#script\nstatic A = 1\nA + 1"), (vec![String::from("A + 1")], 1));
+    assert_eq!(script_main("#script\nstruct A { a: i32 }"), (vec![], 1));
+    // Expressions which end with a block don't need a semicolon
+    assert_eq!(script_main("#script\nif true { print(1); }\nfor i in 0..2 { print(i); }\nprint(2)").0.len(), 3);
+}
+
+#[test]
+fn invalid_scripts() {
+    let codes = |source: &str| parse_statements_str(source).1.iter().map(|err| err.code).collect::<Vec<Option<u16>>>();
+    assert_eq!(codes("#script\nlet a = 1\nprint(a);"), vec![Some(1007)]);
+    assert_eq!(codes("#script\nmain { print(1); }"), vec![Some(1018)]);
+    // Only the very first line can turn on script mode
+    assert!(!codes("let a = 1;").is_empty());
+    assert!(!codes("#scripts\nlet a = 1;").is_empty());
+    assert!(!codes("struct A { a: i32 }\n#script\nlet a = 1;").is_empty());
+}
@@ -0,0 +1,107 @@
+use lazy::errors::*;
+use lazy::errors::builder::{ColorChoice, set_color_choice};
+use lazy::parser::ast::Parser;
+
+fn loc(file: FileId, line: usize, col: usize) -> LoC {
+    LoC { line, col, pos: 0, file }
+}
+
+fn range(file: FileId, start: (usize, usize), end: (usize, usize)) -> Range {
+    loc(file, start.0, start.1).end(&loc(file, end.0, end.1))
+}
+
+fn render(map: &SourceMap, err: &BaseError) -> String {
+    set_color_choice(ColorChoice::Never);
+    map.format_err(err).unwrap()
+}
+
+#[test]
+fn single_line() {
+    let mut map = SourceMap::new();
+    let file = map.add("main.lazy", "main {\n    let a = 5 + b;\n}".to_string());
+    let err = err!(NAME_NOT_FOUND, range(file, (2, 16), (2, 17)), "b");
+    insta::assert_snapshot!(render(&map, &err));
+}
+
+#[test]
+fn tabs_are_expanded() {
+    let mut map = SourceMap::new();
+    let file = map.add("main.lazy", "main {\n\tlet\ta = b;\n}".to_string());
+    let err = err!(NAME_NOT_FOUND, range(file, (2, 9), (2, 10)), "b");
+    insta::assert_snapshot!(render(&map, &err));
+}
+
+#[test]
+fn wide_characters() {
+    let mut map = SourceMap::new();
+    let file = map.add("main.lazy", "let 名前 = \"😀\" + b;".to_string());
+    let err = err!(NAME_NOT_FOUND, range(file, (1, 15), (1, 16)), "b");
+    insta::assert_snapshot!(render(&map, &err));
+}
+
+#[test]
+fn crlf_line_endings() {
+    let mut map = SourceMap::new();
+    let file = map.add("main.lazy", "main {\r\n    let a = 5\r\n}\r\n".to_string());
+    let err = err!(SEMICOLON, range(file, (2, 12), (2, 13)));
+    insta::assert_snapshot!(render(&map, &err));
+}
+
+#[test]
+fn long_lines_are_cut() {
+    let mut map = SourceMap::new();
+    let line = format!("let a = [{}b];", "1, ".repeat(100));
+    let file = map.add("main.lazy", line.clone());
+    let col = line.find('b').unwrap();
+    let err = err!(NAME_NOT_FOUND, range(file, (1, col), (1, col + 1)), "b");
+    insta::assert_snapshot!(render(&map, &err));
+}
+
+#[test]
+fn ranges_past_the_end_of_the_file() {
+    let mut map = SourceMap::new();
+    let file = map.add("main.lazy", "main {\n    let a = 5;\n".to_string());
+    let err = err!(END_OF_BLOCK, range(file, (3, 0), (3, 0)));
+    let default_range = err!(UNEXPECTED_EOF, Range { start: loc(file, 0, 0), end: loc(file, 0, 0) });
+    insta::assert_snapshot!(format!("{}{}", render(&map, &err), render(&map, &default_range)));
+}
+
+#[test]
+fn multi_line_ranges() {
+    let mut map = SourceMap::new();
+    let body = (1..=10).map(|ind| format!("    print({});", ind)).collect::<Vec<String>>().join("\n");
+    let file = map.add("main.lazy", format!("main {{\n{}\n", body));
+    let err = err!(END_OF_BLOCK, range(file, (1, 5), (11, 17)));
+    insta::assert_snapshot!(render(&map, &err));
+}
+
+#[test]
+fn labels_in_other_files() {
+    let mut map = SourceMap::new();
+    let songs = map.add("songs.lazy", "export struct Song {\n    name: str\n}".to_string());
+    let main = map.add("main.lazy", "import * from \"./songs\"\n\nenum Song {}".to_string());
+    let err = err!(DUPLICATE_IDENT, range(main, (3, 5), (3, 9)), "Song")
+        .with_label("First defined here", range(songs, (1, 14), (1, 18)))
+        .with_label("Imported here", range(main, (1, 14), (1, 23)));
+    insta::assert_snapshot!(render(&map, &err));
+}
+
+#[test]
+fn suggestions_and_help() {
+    let mut map = SourceMap::new();
+    let file = map.add("main.lazy", "type A = Mod:Item;".to_string());
+    let err = err!(EXPECTED, range(file, (1, 13), (1, 13)), "Another colon (:)"; ["Module items are accessed with two colons"])
+        .with_suggestion("Add another colon", range(file, (1, 13), (1, 13)), ":", Applicability::MachineApplicable);
+    insta::assert_snapshot!(render(&map, &err));
+}
+
+#[test]
+fn parser_errors() {
+    let mut map = SourceMap::new();
+    let source = "main {\n\tlet a = `😀 ${1 +}`;\n}";
+    let file = map.add("main.lazy", source.to_string());
+    let mut errors = ErrorCollector::new(file);
+    Parser::new(source, &mut errors).parse();
+    let rendered = errors.collected.iter().map(|err| render(&map, err)).collect::<String>();
+    insta::assert_snapshot!(rendered);
+}
@@ -0,0 +1,71 @@
+use lazy::errors::*;
+use lazy::errors::sarif::SarifFormatter;
+use serde_json::{json, Value};
+
+fn range(file: FileId, start: (usize, usize), end: (usize, usize)) -> Range {
+    LoC { line: start.0, col: start.1, pos: start.1, file }.end(&LoC { line: end.0, col: end.1, pos: end.1, file })
+}
+
+fn to_sarif(map: &SourceMap, errors: Vec<BaseError>) -> Value {
+    let mut collector = ErrorCollector::new(FileId::default());
+    collector.collected = errors;
+    serde_json::from_str(&map.format_sarif(&[&collector])).unwrap()
+}
+
+#[test]
+fn log_shape() {
+    let mut map = SourceMap::new();
+    let file = map.add("/project/main.lazy", "let a = b;".to_string());
+    let log = to_sarif(&map, vec![err!(NAME_NOT_FOUND, range(file, (1, 8), (1, 9)), "b")]);
+    assert_eq!(log["version"], "2.1.0");
+    assert_eq!(log["runs"].as_array().unwrap().len(), 1);
+    let run = &log["runs"][0];
+    let result = &run["results"][0];
+    assert_eq!(result["ruleId"], "E2005");
+    assert_eq!(result["level"], "error");
+    let rule_index = result["ruleIndex"].as_u64().unwrap() as usize;
+    assert_eq!(run["tool"]["driver"]["rules"][rule_index]["id"], "E2005");
+    assert_eq!(result["locations"][0]["physicalLocation"], json!({
+        "artifactLocation": { "uri": "file:///project/main.lazy" },
+        "region": { "startLine": 1, "startColumn": 9, "endLine": 1, "endColumn": 10, "byteOffset": 8, "byteLength": 1 }
+    }));
+}
+
+#[test]
+fn relative_paths_use_a_base_id() {
+    let mut map = SourceMap::new();
+    let file = map.add("src/my songs.lazy", "let a = b;".to_string());
+    let windows = map.add("C:\\project\\main.lazy", "let a = b;".to_string());
+    let log = to_sarif(&map, vec![
+        err!(NAME_NOT_FOUND, range(file, (1, 8), (1, 9)), "b"),
+        err!(NAME_NOT_FOUND, range(windows, (1, 8), (1, 9)), "b")
+    ]);
+    let run = &log["runs"][0];
+    assert_eq!(run["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"], json!({ "uri": "src/my%20songs.lazy", "uriBaseId": "SRCROOT" }));
+    assert_eq!(run["results"][1]["locations"][0]["physicalLocation"]["artifactLocation"], json!({ "uri": "file:///C:/project/main.lazy" }));
+    let base = run["originalUriBaseIds"]["SRCROOT"]["uri"].as_str().unwrap();
+    assert!(base.starts_with("file:///") && base.ends_with('/'), "{} isn't an absolute directory uri", base);
+}
+
+#[test]
+fn related_locations_and_fixes() {
+    let mut map = SourceMap::new();
+    let songs = map.add("/project/songs.lazy", "struct Song {}".to_string());
+    let main = map.add("/project/main.lazy", "import { Song } from \"./songs\"".to_string());
+    let err = err!(TYPE_NOT_FOUND_FROM_MOD, range(main, (1, 9), (1, 13)), "Song", "./songs"; ["Export the struct"])
+        .with_label("Declared here", range(songs, (1, 7), (1, 11)))
+        .with_suggestion("Export the struct", range(songs, (1, 0), (1, 0)), "export ", Applicability::MaybeIncorrect);
+    let log = to_sarif(&map, vec![err]);
+    let result = &log["runs"][0]["results"][0];
+    assert!(result["message"]["text"].as_str().unwrap().ends_with("\nHelp: Export the struct"));
+    assert_eq!(result["relatedLocations"][0]["id"], 0);
+    assert_eq!(result["relatedLocations"][0]["message"]["text"], "Declared here");
+    assert_eq!(result["relatedLocations"][0]["physicalLocation"]["artifactLocation"]["uri"], "file:///project/songs.lazy");
+    assert_eq!(result["fixes"][0]["artifactChanges"][0], json!({
+        "artifactLocation": { "uri": "file:///project/songs.lazy" },
+        "replacements": [{
+            "deletedRegion": { "startLine": 1, "startColumn": 1, "endLine": 1, "endColumn": 1, "byteOffset": 0, "byteLength": 0 },
+            "insertedContent": { "text": "export " }
+        }]
+    }));
+}